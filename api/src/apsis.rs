@@ -0,0 +1,178 @@
+use serde::Serialize;
+use swe::Body;
+
+use crate::{
+    ephemeris::Ephemeris,
+    error::Error,
+    lunar_phase::{lunar_phases, Phase},
+    search::crossings,
+};
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// 1天文单位，单位：千米
+pub const AU_KM: f64 = 149_597_870.7;
+
+/// 近地点或远地点
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum ApsisKind {
+    /// 近地点
+    Perigee,
+    /// 远地点
+    Apogee,
+}
+
+impl ApsisKind {
+    /// 由过近地点或远地点之后的距离速度得到类型
+    pub fn from_speed(dist_speed: f64) -> Self {
+        if dist_speed > 0.0 {
+            Self::Perigee
+        } else {
+            Self::Apogee
+        }
+    }
+}
+
+/// 月亮过近地点或远地点
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Apsis {
+    pub kind: ApsisKind,
+    /// UTC时的儒略日
+    pub jd_utc: f64,
+    /// 月地距离，单位：千米
+    pub distance: f64,
+}
+
+/// 朔或望时的月地距离
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Syzygy {
+    pub phase: Phase,
+    /// UTC时的儒略日
+    pub jd_utc: f64,
+    /// 月地距离，单位：千米
+    pub distance: f64,
+}
+
+/// 月地距离，单位：千米
+pub fn moon_distance(ephemeris: &Ephemeris, jd_utc: f64) -> Result<f64, Error> {
+    Ok(ephemeris.calc(jd_utc, &Body::SeMoon)?[2] * AU_KM)
+}
+
+/// 求[start, end]内月亮过近地点、远地点的时刻，即月地距离速度变号的时刻
+/// ephemeris须计算速度
+pub fn lunar_apsides(ephemeris: &Ephemeris, start: f64, end: f64) -> Result<Vec<Apsis>, Error> {
+    let dist_speed = |jd| Ok(ephemeris.calc(jd, &Body::SeMoon)?[5]);
+    // 近地点与远地点间隔约14日
+    crossings(dist_speed, start, end, 1.0)?
+        .into_iter()
+        .map(|jd_utc| -> Result<Apsis, Error> {
+            // 以1小时后的距离速度判断类型
+            let kind = ApsisKind::from_speed(dist_speed(jd_utc + 1.0 / 24.0)?);
+            Ok(Apsis {
+                kind,
+                jd_utc,
+                distance: moon_distance(ephemeris, jd_utc)?,
+            })
+        })
+        .collect()
+}
+
+/// 求[start, end]内的朔、望，及其月地距离
+pub fn syzygies(ephemeris: &Ephemeris, start: f64, end: f64) -> Result<Vec<Syzygy>, Error> {
+    lunar_phases(ephemeris, start, end, &[Phase::NewMoon, Phase::FullMoon])?
+        .into_iter()
+        .map(|(phase, jd_utc)| -> Result<Syzygy, Error> {
+            Ok(Syzygy {
+                phase,
+                jd_utc,
+                distance: moon_distance(ephemeris, jd_utc)?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use swe::Flag;
+
+    use super::{lunar_apsides, syzygies, ApsisKind};
+    use crate::{lunar_phase::Phase, test_util::moshier};
+
+    #[test]
+    fn test_apsis_kind() {
+        // 过近地点后距离增加
+        assert_eq!(ApsisKind::Perigee, ApsisKind::from_speed(0.0001));
+        // 过远地点后距离减小
+        assert_eq!(ApsisKind::Apogee, ApsisKind::from_speed(-0.0001));
+    }
+
+    #[test]
+    fn test_lunar_apsides() {
+        let ephemeris = moshier(vec![Flag::SeflgSpeed]);
+
+        // 2024-04-07 17:50 UTC近地点358850千米，2024-04-20 02:09 UTC远地点405623千米
+        let apsides = lunar_apsides(&ephemeris, 2460400.5, 2460430.5).unwrap();
+        assert_eq!(2, apsides.len());
+
+        let perigee = apsides[0];
+        assert_eq!(ApsisKind::Perigee, perigee.kind);
+        assert!(
+            (perigee.jd_utc - 2460408.2431).abs() < 0.02,
+            "{}",
+            perigee.jd_utc
+        );
+        assert!(
+            (perigee.distance - 358850.0).abs() < 20.0,
+            "{}",
+            perigee.distance
+        );
+
+        let apogee = apsides[1];
+        assert_eq!(ApsisKind::Apogee, apogee.kind);
+        assert!(
+            (apogee.jd_utc - 2460420.5896).abs() < 0.02,
+            "{}",
+            apogee.jd_utc
+        );
+        assert!(
+            (apogee.distance - 405623.0).abs() < 20.0,
+            "{}",
+            apogee.distance
+        );
+    }
+
+    #[test]
+    fn test_syzygies() {
+        let ephemeris = moshier(vec![Flag::SeflgSpeed]);
+
+        let syzygies = syzygies(&ephemeris, 2460400.5, 2460430.5).unwrap();
+        assert_eq!(2, syzygies.len());
+
+        // 2024-04-08 18:21 UTC朔，在近地点之后约1日，月地距离小于默认的360000千米，为超级月亮
+        let new_moon = syzygies[0];
+        assert_eq!(Phase::NewMoon, new_moon.phase);
+        assert!(
+            (new_moon.jd_utc - 2460409.26458).abs() < 2.0 / 1440.0,
+            "{}",
+            new_moon.jd_utc
+        );
+        assert!(
+            new_moon.distance > 358850.0 && new_moon.distance < 360000.0,
+            "{}",
+            new_moon.distance
+        );
+
+        // 2024-04-23 23:49 UTC望，在远地点之后约4日
+        let full_moon = syzygies[1];
+        assert_eq!(Phase::FullMoon, full_moon.phase);
+        assert!(
+            (full_moon.jd_utc - 2460424.49236).abs() < 2.0 / 1440.0,
+            "{}",
+            full_moon.jd_utc
+        );
+        assert!(full_moon.distance > 390000.0, "{}", full_moon.distance);
+    }
+}
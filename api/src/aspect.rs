@@ -0,0 +1,212 @@
+use swe::{swe_degnorm, Body};
+
+use crate::{
+    ephemeris::Ephemeris,
+    error::Error,
+    search::{angle_crossings, angle_diff},
+};
+
+/// 主要相位：合、六合、刑、拱、冲，单位：度
+pub const MAJOR_ASPECTS: [f64; 5] = [0.0, 60.0, 90.0, 120.0, 180.0];
+
+/// 精确相位
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Aspect {
+    /// 相位，单位：度
+    pub angle: f64,
+    /// UTC时的儒略日
+    pub jd_utc: f64,
+    /// 两星体的黄道经度
+    pub long1: f64,
+    pub long2: f64,
+}
+
+/// 某一时刻，两星体所成的相位
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct AspectSample {
+    /// UTC时的儒略日
+    pub jd_utc: f64,
+    /// 相位，单位：度
+    pub angle: f64,
+    /// 与精确相位之差，单位：度
+    pub orb: f64,
+    /// 是否为入相位，否则为出相位
+    pub applying: bool,
+}
+
+/// 相位归算到[0, 180]
+pub fn normalize_aspect(angle: f64) -> f64 {
+    let angle = swe_degnorm(angle);
+    angle.min(360.0 - angle)
+}
+
+/// 角距separation（[-180, 180)）与angles中最接近的相位，及其差
+fn nearest_aspect(separation: f64, angles: &[f64]) -> Option<(f64, f64)> {
+    angles
+        .iter()
+        .map(|&angle| (angle, (separation.abs() - angle).abs()))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// 两星体的黄道经度，及二者的角距，角距归算到[-180, 180)
+fn separation(
+    ephemeris: &Ephemeris,
+    jd_utc: f64,
+    body1: &Body,
+    body2: &Body,
+) -> Result<(f64, f64, f64), Error> {
+    // 两星体只设置一次星历表
+    let xx = ephemeris.calc_bodies(jd_utc, &[body1, body2])?;
+    let (long1, long2) = (xx[0][0], xx[1][0]);
+    Ok((long1, long2, angle_diff(long1, long2)))
+}
+
+/// 求[start, end]内两星体成angles中相位的精确时刻，按时间排序
+pub fn aspects(
+    ephemeris: &Ephemeris,
+    body1: &Body,
+    body2: &Body,
+    angles: &[f64],
+    start: f64,
+    end: f64,
+) -> Result<Vec<Aspect>, Error> {
+    // 有月亮时，角距每日变化约12度
+    let step = if matches!(body1, Body::SeMoon) || matches!(body2, Body::SeMoon) {
+        0.25
+    } else {
+        1.0
+    };
+    let f = |jd| Ok(separation(ephemeris, jd, body1, body2)?.2);
+
+    let mut aspects = vec![];
+    for &angle in angles {
+        // 合、冲只有一种角距，其余相位角距可正可负
+        let targets = if angle == 0.0 || angle == 180.0 {
+            vec![angle]
+        } else {
+            vec![angle, -angle]
+        };
+        for target in targets {
+            for jd_utc in angle_crossings(f, target, start, end, step)? {
+                let (long1, long2, _) = separation(ephemeris, jd_utc, body1, body2)?;
+                aspects.push(Aspect {
+                    angle,
+                    jd_utc,
+                    long1,
+                    long2,
+                });
+            }
+        }
+    }
+    aspects.sort_by(|a, b| a.jd_utc.total_cmp(&b.jd_utc));
+    Ok(aspects)
+}
+
+/// 在[start, end]内求两星体所成的相位，区间小于1天，步长为1小时，否则步长为1天
+/// 仅给出与angles中相位之差不超过orb的时刻
+pub fn aspect_samples(
+    ephemeris: &Ephemeris,
+    body1: &Body,
+    body2: &Body,
+    angles: &[f64],
+    orb: f64,
+    start: f64,
+    end: f64,
+) -> Result<Vec<AspectSample>, Error> {
+    let step = if end - start < 1.0 { 1.0 / 24.0 } else { 1.0 };
+    let mut samples = vec![];
+    let mut jd_utc = start;
+    while jd_utc < end {
+        let (_, _, sep) = separation(ephemeris, jd_utc, body1, body2)?;
+        if let Some((angle, d)) = nearest_aspect(sep, angles).filter(|(_, d)| *d <= orb) {
+            // 与1小时后比较，相位之差减小为入相位
+            let (_, _, next) = separation(ephemeris, jd_utc + 1.0 / 24.0, body1, body2)?;
+            samples.push(AspectSample {
+                jd_utc,
+                angle,
+                orb: d,
+                applying: (next.abs() - angle).abs() < d,
+            });
+        }
+        jd_utc += step;
+    }
+    Ok(samples)
+}
+
+#[cfg(test)]
+mod test {
+    use swe::Body;
+
+    use super::{aspect_samples, aspects, nearest_aspect, normalize_aspect, MAJOR_ASPECTS};
+    use crate::test_util::moshier;
+
+    #[test]
+    fn test_normalize_aspect() {
+        assert_eq!(0.0, normalize_aspect(0.0));
+        assert_eq!(0.0, normalize_aspect(360.0));
+        assert_eq!(90.0, normalize_aspect(270.0));
+        assert_eq!(60.0, normalize_aspect(-60.0));
+        assert_eq!(180.0, normalize_aspect(180.0));
+    }
+
+    #[test]
+    fn test_nearest_aspect() {
+        assert_eq!(Some((0.0, 3.0)), nearest_aspect(-3.0, &MAJOR_ASPECTS));
+        assert_eq!(Some((90.0, 2.0)), nearest_aspect(-88.0, &MAJOR_ASPECTS));
+        assert_eq!(Some((180.0, 1.0)), nearest_aspect(-179.0, &MAJOR_ASPECTS));
+        assert_eq!(Some((120.0, 5.0)), nearest_aspect(125.0, &MAJOR_ASPECTS));
+        assert_eq!(None, nearest_aspect(10.0, &[]));
+    }
+
+    #[test]
+    fn test_great_conjunction() {
+        let ephemeris = moshier(vec![]);
+
+        // 2020-12-21 18:20 UTC，木星、土星合于宝瓶0°29′
+        let found = aspects(
+            &ephemeris,
+            &Body::SeJupiter,
+            &Body::SeSaturn,
+            &MAJOR_ASPECTS,
+            2459198.5,
+            2459212.5,
+        )
+        .unwrap();
+        assert_eq!(1, found.len());
+        let conjunction = found[0];
+        assert_eq!(0.0, conjunction.angle);
+        assert!(
+            (conjunction.jd_utc - 2459205.2639).abs() < 0.05,
+            "{}",
+            conjunction.jd_utc
+        );
+        assert!(
+            (conjunction.long1 - 300.49).abs() < 0.05,
+            "{}",
+            conjunction.long1
+        );
+        assert!((conjunction.long1 - conjunction.long2).abs() < 1e-4);
+
+        // 合相前为入相位，合相后为出相位
+        let samples = aspect_samples(
+            &ephemeris,
+            &Body::SeJupiter,
+            &Body::SeSaturn,
+            &[0.0],
+            1.0,
+            2459201.5,
+            2459209.5,
+        )
+        .unwrap();
+        assert_eq!(8, samples.len());
+        for sample in samples {
+            assert_eq!(0.0, sample.angle);
+            assert_eq!(
+                sample.jd_utc < conjunction.jd_utc,
+                sample.applying,
+                "{}",
+                sample.jd_utc
+            );
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use swe::Body;
+
+use crate::error::Error;
+
+/// 由名称得到星体
+/// 名称不区分大小写
+pub fn body_from_name(name: &str) -> Result<Body, Error> {
+    let body = match name.to_lowercase().as_str() {
+        "sun" => Body::SeSun,
+        "moon" => Body::SeMoon,
+        "mercury" => Body::SeMercury,
+        "venus" => Body::SeVenus,
+        "mars" => Body::SeMars,
+        "jupiter" => Body::SeJupiter,
+        "saturn" => Body::SeSaturn,
+        "uranus" => Body::SeUranus,
+        "neptune" => Body::SeNeptune,
+        "pluto" => Body::SePluto,
+        "mean_node" => Body::SeMeanNode,
+        "true_node" => Body::SeTrueNode,
+        "mean_apog" => Body::SeMeanApog,
+        "oscu_apog" => Body::SeOscuApog,
+        "earth" => Body::SeEarth,
+        "chiron" => Body::SeChiron,
+        "pholus" => Body::SePholus,
+        "ceres" => Body::SeCeres,
+        "pallas" => Body::SePallas,
+        "juno" => Body::SeJuno,
+        "vesta" => Body::SeVesta,
+        _ => return Err(Error::BadRequest(format!("不支持的星体:{name}"))),
+    };
+    Ok(body)
+}
+
+/// 由名称得到有升落的星体
+/// 月交点、月远地点为轨道上的点，地心坐标中没有地球，均无升落
+pub fn rising_body_from_name(name: &str) -> Result<Body, Error> {
+    let body = body_from_name(name)?;
+    if matches!(
+        body,
+        Body::SeMeanNode | Body::SeTrueNode | Body::SeMeanApog | Body::SeOscuApog | Body::SeEarth
+    ) {
+        return Err(Error::BadRequest(format!(
+            "{}没有升落:{name}",
+            body_name(&body)
+        )));
+    }
+    Ok(body)
+}
+
+/// 星体的中文名，用于错误信息
+pub fn body_name(body: &Body) -> &'static str {
+    match body {
+        Body::SeSun => "太阳",
+        Body::SeMoon => "月亮",
+        Body::SeMercury => "水星",
+        Body::SeVenus => "金星",
+        Body::SeMars => "火星",
+        Body::SeJupiter => "木星",
+        Body::SeSaturn => "土星",
+        Body::SeUranus => "天王星",
+        Body::SeNeptune => "海王星",
+        Body::SePluto => "冥王星",
+        Body::SeMeanNode => "平均月交点",
+        Body::SeTrueNode => "真月交点",
+        Body::SeMeanApog => "平均月远地点",
+        Body::SeOscuApog => "密切月远地点",
+        Body::SeEarth => "地球",
+        Body::SeChiron => "凯龙星",
+        Body::SePholus => "人龙星",
+        Body::SeCeres => "谷神星",
+        Body::SePallas => "智神星",
+        Body::SeJuno => "婚神星",
+        Body::SeVesta => "灶神星",
+        _ => "星体",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{body_from_name, rising_body_from_name};
+
+    #[test]
+    fn test_body_from_name() {
+        let names = [
+            "sun",
+            "moon",
+            "mercury",
+            "venus",
+            "mars",
+            "jupiter",
+            "saturn",
+            "uranus",
+            "neptune",
+            "pluto",
+            "mean_node",
+            "true_node",
+            "mean_apog",
+            "oscu_apog",
+            "earth",
+            "chiron",
+            "pholus",
+            "ceres",
+            "pallas",
+            "juno",
+            "vesta",
+        ];
+        for name in names {
+            assert!(body_from_name(name).is_ok(), "{name}");
+        }
+
+        // 不区分大小写
+        assert!(body_from_name("Sun").is_ok());
+        assert!(body_from_name("MOON").is_ok());
+
+        // 不支持的星体
+        assert!(body_from_name("vulcan").is_err());
+        assert!(body_from_name("").is_err());
+    }
+
+    #[test]
+    fn test_rising_body_from_name() {
+        for name in ["sun", "moon", "venus", "pluto", "ceres"] {
+            assert!(rising_body_from_name(name).is_ok(), "{name}");
+        }
+
+        // 无升落
+        for name in ["mean_node", "true_node", "mean_apog", "oscu_apog", "earth"] {
+            assert!(rising_body_from_name(name).is_err(), "{name}");
+        }
+        assert!(rising_body_from_name("vulcan").is_err());
+    }
+}
@@ -0,0 +1,417 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{ephemeris::Ephemeris, error::Error};
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+// 交食类型，与SE_ECL_*相同
+const SE_ECL_TOTAL: i32 = 4;
+const SE_ECL_ANNULAR: i32 = 8;
+const SE_ECL_PARTIAL: i32 = 16;
+const SE_ECL_ANNULAR_TOTAL: i32 = 32;
+const SE_ECL_PENUMBRAL: i32 = 64;
+
+/// 日食的初亏、食既、生光、复圆，及其在swe_sol_eclipse_when_loc()结果中的序号
+const SOLAR_CONTACTS: [(&str, usize); 4] = [("c1", 1), ("c2", 2), ("c3", 3), ("c4", 4)];
+
+/// 月食的半影食始、初亏、食既、生光、复圆、半影食终，及其在swe_lun_eclipse_when()结果中的序号
+const LUNAR_CONTACTS: [(&str, usize); 6] = [
+    ("p1", 6),
+    ("u1", 2),
+    ("u2", 4),
+    ("u3", 5),
+    ("u4", 3),
+    ("p4", 7),
+];
+
+/// 日食或月食
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum EclipseKind {
+    /// 日食
+    Solar,
+    /// 月食
+    Lunar,
+}
+
+/// 食的类型
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum EclipseType {
+    /// 全食
+    Total,
+    /// 环食
+    Annular,
+    /// 全环食
+    Hybrid,
+    /// 偏食
+    Partial,
+    /// 半影月食
+    Penumbral,
+}
+
+impl EclipseType {
+    /// 由swe_*_eclipse_*()返回的标志得到食的类型
+    pub fn from_flags(flags: i32) -> Option<Self> {
+        if flags & SE_ECL_ANNULAR_TOTAL != 0 {
+            Some(Self::Hybrid)
+        } else if flags & SE_ECL_TOTAL != 0 {
+            Some(Self::Total)
+        } else if flags & SE_ECL_ANNULAR != 0 {
+            Some(Self::Annular)
+        } else if flags & SE_ECL_PARTIAL != 0 {
+            Some(Self::Partial)
+        } else if flags & SE_ECL_PENUMBRAL != 0 {
+            Some(Self::Penumbral)
+        } else {
+            None
+        }
+    }
+}
+
+/// 交食
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Eclipse {
+    pub kind: EclipseKind,
+    pub eclipse_type: EclipseType,
+    /// 食甚，UTC时的儒略日
+    pub jd_utc: f64,
+    /// 食分
+    /// 日食为食甚时月亮遮住太阳直径的比例，月食为本影食分，半影月食为半影食分
+    pub magnitude: f64,
+    /// 沙罗序列号
+    pub saros: i32,
+    /// 在沙罗序列中的序号
+    pub saros_member: i32,
+}
+
+/// 观测者所在地的交食时刻
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Contact {
+    /// 名称，日食为c1-c4，月食为p1、u1-u4、p4
+    pub name: &'static str,
+    /// UTC时的儒略日
+    pub jd_utc: f64,
+    /// 太阳或月亮的真高度，单位：度
+    pub altitude: f64,
+    /// 计大气折射后，太阳或月亮是否在地平线上
+    pub visible: bool,
+}
+
+/// 观测者所在地的交食情况
+#[derive(Clone, PartialEq, Debug)]
+pub struct LocalEclipse {
+    /// 全球的交食
+    pub eclipse: Eclipse,
+    /// 当地所见的食的类型，当地不可见时为None
+    pub eclipse_type: Option<EclipseType>,
+    /// 当地的食分
+    pub magnitude: f64,
+    /// 当地食甚时太阳面积被遮住的比例，仅用于日食
+    pub obscuration: Option<f64>,
+    /// 当地的食甚，当地不可见时为None
+    pub maximum: Option<Contact>,
+    /// 初亏、复圆等时刻，按时间排序
+    pub contacts: Vec<Contact>,
+}
+
+impl LocalEclipse {
+    /// 当地是否可见，即食甚或任一时刻太阳或月亮在地平线上
+    pub fn is_visible(&self) -> bool {
+        self.maximum.iter().chain(&self.contacts).any(|c| c.visible)
+    }
+}
+
+/// 求jd_utc之后的下一次日食
+/// 返回日食，及swe_sol_eclipse_when_glob()给出的各时刻
+fn next_solar_eclipse(ephemeris: &Ephemeris, jd_utc: f64) -> Result<(Eclipse, [f64; 10]), Error> {
+    let (flags, tret) = ephemeris.sol_eclipse_when_glob(jd_utc)?;
+    let (_, _, attr) = ephemeris.sol_eclipse_where(tret[0])?;
+    let eclipse_type = EclipseType::from_flags(flags)
+        .ok_or_else(|| Error::Function(format!("未知的日食类型:{flags}")))?;
+    let eclipse = Eclipse {
+        kind: EclipseKind::Solar,
+        eclipse_type,
+        jd_utc: tret[0],
+        magnitude: attr[8],
+        saros: attr[9] as i32,
+        saros_member: attr[10] as i32,
+    };
+    Ok((eclipse, tret))
+}
+
+/// 求jd_utc之后的下一次月食
+/// 返回月食，及swe_lun_eclipse_when()给出的各时刻
+fn next_lunar_eclipse(ephemeris: &Ephemeris, jd_utc: f64) -> Result<(Eclipse, [f64; 10]), Error> {
+    let (flags, tret) = ephemeris.lun_eclipse_when(jd_utc)?;
+    let (_, attr) = ephemeris.lun_eclipse_how(tret[0], [0.0, 0.0, 0.0])?;
+    let eclipse_type = EclipseType::from_flags(flags)
+        .ok_or_else(|| Error::Function(format!("未知的月食类型:{flags}")))?;
+    let magnitude = if eclipse_type == EclipseType::Penumbral {
+        attr[1]
+    } else {
+        attr[0]
+    };
+    let eclipse = Eclipse {
+        kind: EclipseKind::Lunar,
+        eclipse_type,
+        jd_utc: tret[0],
+        magnitude,
+        saros: attr[9] as i32,
+        saros_member: attr[10] as i32,
+    };
+    Ok((eclipse, tret))
+}
+
+/// 求[start, end]内的日食
+pub fn solar_eclipses(ephemeris: &Ephemeris, start: f64, end: f64) -> Result<Vec<Eclipse>, Error> {
+    let mut eclipses = vec![];
+    let mut jd = start;
+    loop {
+        let (eclipse, _) = next_solar_eclipse(ephemeris, jd)?;
+        if eclipse.jd_utc > end {
+            break;
+        }
+        eclipses.push(eclipse);
+        // 相邻两次日食至少间隔约1个朔望月
+        jd = eclipse.jd_utc + 1.0;
+    }
+    Ok(eclipses)
+}
+
+/// 求[start, end]内的月食
+pub fn lunar_eclipses(ephemeris: &Ephemeris, start: f64, end: f64) -> Result<Vec<Eclipse>, Error> {
+    let mut eclipses = vec![];
+    let mut jd = start;
+    loop {
+        let (eclipse, _) = next_lunar_eclipse(ephemeris, jd)?;
+        if eclipse.jd_utc > end {
+            break;
+        }
+        eclipses.push(eclipse);
+        jd = eclipse.jd_utc + 1.0;
+    }
+    Ok(eclipses)
+}
+
+/// 求[start, end]内的日食、月食，按时间排序
+pub fn eclipses(ephemeris: &Ephemeris, start: f64, end: f64) -> Result<Vec<Eclipse>, Error> {
+    let mut eclipses = solar_eclipses(ephemeris, start, end)?;
+    eclipses.extend(lunar_eclipses(ephemeris, start, end)?);
+    eclipses.sort_by(|a, b| a.jd_utc.total_cmp(&b.jd_utc));
+    Ok(eclipses)
+}
+
+/// 观测者所在地，jd_utc之后下一次日食或月食的情况
+/// geopos: 观测者的地理经度、纬度、海拔
+pub fn local_eclipse(
+    ephemeris: &Ephemeris,
+    kind: EclipseKind,
+    jd_utc: f64,
+    geopos: [f64; 3],
+) -> Result<LocalEclipse, Error> {
+    match kind {
+        EclipseKind::Solar => local_solar_eclipse(ephemeris, jd_utc, geopos),
+        EclipseKind::Lunar => local_lunar_eclipse(ephemeris, jd_utc, geopos),
+    }
+}
+
+fn local_solar_eclipse(
+    ephemeris: &Ephemeris,
+    jd_utc: f64,
+    geopos: [f64; 3],
+) -> Result<LocalEclipse, Error> {
+    let (eclipse, tret) = next_solar_eclipse(ephemeris, jd_utc)?;
+
+    // 自全球初亏起查找当地可见的日食，其食甚在全球复圆之后，则当地不可见
+    let (flags, tret_loc, attr) = ephemeris.sol_eclipse_when_loc(tret[2], geopos)?;
+    if tret_loc[0] > tret[3] {
+        return Ok(LocalEclipse {
+            eclipse,
+            eclipse_type: None,
+            magnitude: 0.0,
+            obscuration: None,
+            maximum: None,
+            contacts: vec![],
+        });
+    }
+
+    let maximum = contact(ephemeris, EclipseKind::Solar, "max", tret_loc[0], geopos)?;
+    let contacts = contact_times(&tret_loc, &SOLAR_CONTACTS)
+        .into_iter()
+        .map(|(name, jd)| contact(ephemeris, EclipseKind::Solar, name, jd, geopos))
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(LocalEclipse {
+        eclipse,
+        eclipse_type: EclipseType::from_flags(flags),
+        magnitude: attr[8],
+        obscuration: Some(attr[2]),
+        maximum: Some(maximum),
+        contacts,
+    })
+}
+
+fn local_lunar_eclipse(
+    ephemeris: &Ephemeris,
+    jd_utc: f64,
+    geopos: [f64; 3],
+) -> Result<LocalEclipse, Error> {
+    // 月食各时刻全球相同，各地仅月亮高度不同
+    let (eclipse, tret) = next_lunar_eclipse(ephemeris, jd_utc)?;
+
+    let maximum = contact(ephemeris, EclipseKind::Lunar, "max", tret[0], geopos)?;
+    let contacts = contact_times(&tret, &LUNAR_CONTACTS)
+        .into_iter()
+        .map(|(name, jd)| contact(ephemeris, EclipseKind::Lunar, name, jd, geopos))
+        .collect::<Result<Vec<_>, Error>>()?;
+    let mut local = LocalEclipse {
+        eclipse,
+        eclipse_type: Some(eclipse.eclipse_type),
+        magnitude: eclipse.magnitude,
+        obscuration: None,
+        maximum: Some(maximum),
+        contacts,
+    };
+    if !local.is_visible() {
+        local.eclipse_type = None;
+    }
+    Ok(local)
+}
+
+/// 计算交食某一时刻，太阳或月亮在观测者所在地的高度
+fn contact(
+    ephemeris: &Ephemeris,
+    kind: EclipseKind,
+    name: &'static str,
+    jd_utc: f64,
+    geopos: [f64; 3],
+) -> Result<Contact, Error> {
+    let (_, attr) = match kind {
+        EclipseKind::Solar => ephemeris.sol_eclipse_how(jd_utc, geopos)?,
+        EclipseKind::Lunar => ephemeris.lun_eclipse_how(jd_utc, geopos)?,
+    };
+    Ok(Contact {
+        name,
+        jd_utc,
+        altitude: attr[5],
+        visible: attr[6] > 0.0,
+    })
+}
+
+/// 由swe_*_eclipse_when*()给出的各时刻，得到发生的交食时刻
+/// 不发生的时刻（如偏食的食既、生光）为0，略去
+fn contact_times(tret: &[f64; 10], contacts: &[(&'static str, usize)]) -> Vec<(&'static str, f64)> {
+    contacts
+        .iter()
+        .filter(|(_, i)| tret[*i] != 0.0)
+        .map(|&(name, i)| (name, tret[i]))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        contact_times, eclipses, local_eclipse, EclipseKind, EclipseType, LUNAR_CONTACTS,
+        SOLAR_CONTACTS,
+    };
+    use crate::test_util::moshier;
+
+    #[test]
+    fn test_eclipse_type_from_flags() {
+        // SE_ECL_CENTRAL | SE_ECL_TOTAL
+        assert_eq!(Some(EclipseType::Total), EclipseType::from_flags(1 | 4));
+        // SE_ECL_CENTRAL | SE_ECL_ANNULAR
+        assert_eq!(Some(EclipseType::Annular), EclipseType::from_flags(1 | 8));
+        // SE_ECL_CENTRAL | SE_ECL_ANNULAR_TOTAL
+        assert_eq!(Some(EclipseType::Hybrid), EclipseType::from_flags(1 | 32));
+        // SE_ECL_NONCENTRAL | SE_ECL_PARTIAL
+        assert_eq!(Some(EclipseType::Partial), EclipseType::from_flags(2 | 16));
+        assert_eq!(Some(EclipseType::Penumbral), EclipseType::from_flags(64));
+        assert_eq!(None, EclipseType::from_flags(0));
+    }
+
+    #[test]
+    fn test_contact_times() {
+        // 月偏食，没有食既、生光
+        let tret = [5.0, 0.0, 3.0, 7.0, 0.0, 0.0, 1.0, 9.0, 0.0, 0.0];
+        let contacts = contact_times(&tret, &LUNAR_CONTACTS);
+        assert_eq!(
+            vec![("p1", 1.0), ("u1", 3.0), ("u4", 7.0), ("p4", 9.0)],
+            contacts
+        );
+
+        // 日全食
+        let tret = [5.0, 3.0, 4.9, 5.1, 7.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+        let contacts = contact_times(&tret, &SOLAR_CONTACTS);
+        assert_eq!(
+            vec![("c1", 3.0), ("c2", 4.9), ("c3", 5.1), ("c4", 7.0)],
+            contacts
+        );
+    }
+
+    #[test]
+    fn test_eclipses() {
+        let ephemeris = moshier(vec![]);
+
+        // 2024-03-20至2024-04-30：2024-03-25 07:13半影月食，2024-04-08 18:17日全食（UTC）
+        let eclipses = eclipses(&ephemeris, 2460389.5, 2460430.5).unwrap();
+        assert_eq!(2, eclipses.len());
+
+        let lunar = eclipses[0];
+        assert_eq!(EclipseKind::Lunar, lunar.kind);
+        assert_eq!(EclipseType::Penumbral, lunar.eclipse_type);
+        assert!(
+            (lunar.jd_utc - 2460394.80069).abs() < 5.0 / 1440.0,
+            "{}",
+            lunar.jd_utc
+        );
+
+        // 沙罗序列139的第30次
+        let solar = eclipses[1];
+        assert_eq!(EclipseKind::Solar, solar.kind);
+        assert_eq!(EclipseType::Total, solar.eclipse_type);
+        assert!(
+            (solar.jd_utc - 2460409.26181).abs() < 2.0 / 1440.0,
+            "{}",
+            solar.jd_utc
+        );
+        assert!(
+            (solar.magnitude - 1.0566).abs() < 0.001,
+            "{}",
+            solar.magnitude
+        );
+        assert_eq!(139, solar.saros);
+        assert_eq!(30, solar.saros_member);
+    }
+
+    #[test]
+    fn test_local_solar_eclipse() {
+        let ephemeris = moshier(vec![]);
+
+        // 美国达拉斯，在2024-04-08日全食的全食带内
+        // 初亏17:23，食既18:40，生光18:44，复圆20:02（UTC）
+        let geopos = [-96.80, 32.78, 140.0];
+        let local = local_eclipse(&ephemeris, EclipseKind::Solar, 2460401.5, geopos).unwrap();
+        assert_eq!(EclipseType::Total, local.eclipse.eclipse_type);
+        assert_eq!(Some(EclipseType::Total), local.eclipse_type);
+        assert!(local.magnitude > 1.0, "{}", local.magnitude);
+        assert!(local.is_visible());
+
+        let names: Vec<&str> = local.contacts.iter().map(|c| c.name).collect();
+        assert_eq!(vec!["c1", "c2", "c3", "c4"], names);
+        let maximum = local.maximum.unwrap();
+        let jds: Vec<f64> = local.contacts.iter().map(|c| c.jd_utc).collect();
+        assert!(jds[0] < jds[1] && jds[1] < maximum.jd_utc);
+        assert!(maximum.jd_utc < jds[2] && jds[2] < jds[3]);
+        // 太阳始终在地平线上
+        assert!(local.contacts.iter().all(|c| c.visible && c.altitude > 0.0));
+
+        let expected = [2460409.22431, 2460409.27778, 2460409.28056, 2460409.33472];
+        for (jd, expected) in jds.iter().zip(expected) {
+            assert!((jd - expected).abs() < 3.0 / 1440.0, "{jd}");
+        }
+    }
+}
@@ -0,0 +1,685 @@
+use std::{cell::Cell, path::Path};
+
+use swe::{
+    swe_calc_ut, swe_close, swe_fixstar2_ut, swe_get_ayanamsa_ut, swe_lun_eclipse_how,
+    swe_lun_eclipse_when, swe_rise_trans_true_hor, swe_set_ephe_path, swe_set_jpl_file,
+    swe_set_sid_mode, swe_set_topo, swe_sol_eclipse_how, swe_sol_eclipse_when_glob,
+    swe_sol_eclipse_when_loc, swe_sol_eclipse_where, swe_time_equ, Body, Flag,
+};
+
+use crate::{
+    body::body_name,
+    error::Error,
+    request::{Ayanamsa, Coordinate, DateRangeRequest, Frame, Observer, Output, Zodiac},
+    rise_set::Horizon,
+    state::{AppState, Backend},
+};
+
+/// 响应头，给出实际使用的星历表
+pub const BACKEND_HEADER: &str = "X-Ephemeris-Backend";
+
+/// 瑞士星历表文件每个覆盖600年
+const SWISS_FILE_YEARS: i32 = 600;
+
+/// EPHE_PATH中多个路径的分隔符，与swe_set_ephe_path()相同
+const PATH_SEPARATORS: &[char] = if cfg!(windows) { &[';'] } else { &[';', ':'] };
+
+/// 星历表计算参数
+/// 由AppState及请求得到，每次计算前设置星历表路径、观测者位置及岁差
+pub struct Ephemeris<'a> {
+    ephe_path: &'a str,
+    backend: Backend,
+    jpl_file: Option<&'a str>,
+    fallback: bool,
+    /// 实际使用的星历表，改用Moshier星历表计算后为Moshier
+    used: Cell<Backend>,
+    /// 最近一次检查的星历表文件所覆盖的年代，及文件是否存在
+    files: Cell<Option<(i32, bool)>>,
+    flags: Vec<Flag>,
+    observer: Option<Observer>,
+    /// 岁差，SE_SIDM_*
+    sid_mode: i32,
+}
+
+impl<'a> Ephemeris<'a> {
+    /// 以给定的计算标志构造，地心、回归黄道
+    pub fn with_flags(app_state: &'a AppState, flags: Vec<Flag>) -> Self {
+        Self {
+            ephe_path: &app_state.ephe_path,
+            backend: app_state.backend,
+            jpl_file: app_state.jpl_file.as_deref(),
+            fallback: app_state.fallback,
+            used: Cell::new(app_state.backend),
+            files: Cell::new(None),
+            flags,
+            observer: None,
+            sid_mode: 0,
+        }
+    }
+
+    pub fn new(app_state: &'a AppState, r: &DateRangeRequest) -> Result<Self, Error> {
+        if r.observer.is_some() && r.frame != Frame::Geocentric {
+            return Err(Error::BadRequest(
+                "站心坐标仅可用于frame=geocentric".to_string(),
+            ));
+        }
+        if r.zodiac == Zodiac::Sidereal && r.coordinate != Coordinate::Ecliptic {
+            return Err(Error::BadRequest(
+                "恒星黄道仅可用于coordinate=ecliptic".to_string(),
+            ));
+        }
+        // 恒星黄道以当日春分点减去岁差值，不能再归算到J2000
+        if r.zodiac == Zodiac::Sidereal && r.position.j2000 {
+            return Err(Error::BadRequest(
+                "恒星黄道不能与position.j2000同时使用".to_string(),
+            ));
+        }
+
+        let mut flags = vec![];
+        if r.output == Output::Full {
+            flags.push(Flag::SeflgSpeed);
+        }
+        match r.coordinate {
+            Coordinate::Ecliptic => {}
+            Coordinate::Equatorial => flags.push(Flag::SeflgEquatorial),
+            Coordinate::EquatorialJ2000 => {
+                flags.push(Flag::SeflgEquatorial);
+                flags.push(Flag::SeflgJ2000);
+            }
+        }
+        match r.frame {
+            Frame::Geocentric => {}
+            Frame::Heliocentric => flags.push(Flag::SeflgHelctr),
+            Frame::Barycentric => flags.push(Flag::SeflgBaryctr),
+        }
+        if r.observer.is_some() {
+            flags.push(Flag::SeflgTopoctr);
+        }
+        if r.zodiac == Zodiac::Sidereal {
+            flags.push(Flag::SeflgSidereal);
+        }
+        if r.position.no_aberration {
+            flags.push(Flag::SeflgNoaberr);
+        }
+        // SEFLG_TRUEPOS给出几何位置，已包含不计光行差与光线偏折
+        if r.position.no_light_time {
+            flags.push(Flag::SeflgTruepos);
+        }
+        if r.position.no_deflection {
+            flags.push(Flag::SeflgNogdefl);
+        }
+        if r.position.no_nutation {
+            flags.push(Flag::SeflgNonut);
+        }
+        if r.position.j2000 && r.coordinate != Coordinate::EquatorialJ2000 {
+            flags.push(Flag::SeflgJ2000);
+        }
+        if r.position.icrs {
+            flags.push(Flag::SeflgIcrs);
+        }
+
+        let mut ephemeris = Self::with_flags(app_state, flags).with_ayanamsa(r.ayanamsa);
+        ephemeris.observer = r.observer;
+        Ok(ephemeris)
+    }
+
+    /// 设置岁差
+    pub fn with_ayanamsa(mut self, ayanamsa: Ayanamsa) -> Self {
+        self.sid_mode = ayanamsa as i32;
+        self
+    }
+
+    /// 实际使用的星历表
+    /// 只要有一次改用Moshier星历表计算，即为Moshier
+    pub fn backend(&self) -> Backend {
+        self.used.get()
+    }
+
+    /// 响应头，给出实际使用的星历表
+    pub fn backend_header(&self) -> (&'static str, String) {
+        (BACKEND_HEADER, self.backend().to_string())
+    }
+
+    /// 计算星体位置
+    /// 返回swe_calc_ut()的计算结果：经度、纬度、距离、经度速度、纬度速度、距离速度
+    pub fn calc(&self, jd_utc: f64, body: &Body) -> Result<[f64; 6], Error> {
+        self.with_backend(jd_utc, |flags| swe_calc_ut(jd_utc, body, flags))
+            .map_err(|e| Error::Function(format!("计算{}位置错误:{e}", body_name(body))))
+    }
+
+    /// 计算多个星体的位置，只设置一次星历表
+    /// 返回各星体swe_calc_ut()的计算结果，顺序与bodies相同
+    pub fn calc_bodies(&self, jd_utc: f64, bodies: &[&Body]) -> Result<Vec<[f64; 6]>, Error> {
+        self.with_backend(jd_utc, |flags| {
+            bodies
+                .iter()
+                .map(|&body| swe_calc_ut(jd_utc, body, flags))
+                .collect()
+        })
+        .map_err(|e| Error::Function(format!("计算星体位置错误:{e}")))
+    }
+
+    /// 计算星体的赤纬，单位：度
+    pub fn declination(&self, jd_utc: f64, body: &Body) -> Result<f64, Error> {
+        self.with_backend(jd_utc, |flags| {
+            let mut flags = flags.to_vec();
+            flags.push(Flag::SeflgEquatorial);
+            swe_calc_ut(jd_utc, body, &flags)
+        })
+        .map(|xx| xx[1])
+        .map_err(|e| Error::Function(format!("计算{}赤纬错误:{e}", body_name(body))))
+    }
+
+    /// 求jd_utc之后，星体的下一次升、落或中天
+    /// rsmi: SE_CALC_*、SE_BIT_*标志
+    /// 返回swe_rise_trans_true_hor()求得的时刻，星体不升不落时为None
+    pub fn rise_trans(
+        &self,
+        jd_utc: f64,
+        body: &Body,
+        rsmi: i32,
+        horizon: &Horizon,
+    ) -> Result<Option<f64>, Error> {
+        // 气压为0时由海拔估算，气温取10摄氏度
+        self.with_backend(jd_utc, |flags| {
+            swe_rise_trans_true_hor(
+                jd_utc,
+                body,
+                flags,
+                rsmi,
+                &horizon.geopos,
+                0.0,
+                10.0,
+                horizon.height,
+            )
+        })
+        // 返回-2时，星体不升不落
+        .map(|(ret, tret)| (ret != -2).then_some(tret))
+        .map_err(|e| Error::Function(format!("计算{}升落错误:{e}", body_name(body))))
+    }
+
+    /// 计算恒星在各时刻的位置
+    /// star: 恒星名称，如：Spica，或星表中的序号
+    /// 星历表文件相同的各时刻只读取一次星表，星表中没有该恒星时返回BadRequest
+    pub fn calc_star_series(&self, jds: &[f64], star: &str) -> Result<Vec<[f64; 6]>, Error> {
+        let mut series = Vec::with_capacity(jds.len());
+        for chunk in jds.chunk_by(|a, b| swiss_file_block(*a) == swiss_file_block(*b)) {
+            // 没有该恒星时不必改用Moshier星历表重算
+            let xxs = self
+                .with_backend(chunk[0], |flags| {
+                    let mut xxs = vec![];
+                    for &jd_utc in chunk {
+                        match swe_fixstar2_ut(star, jd_utc, flags) {
+                            Ok((_, xx)) => xxs.push(xx),
+                            Err(e) if is_star_not_found(&e) => return Ok(None),
+                            Err(e) => return Err(e),
+                        }
+                    }
+                    Ok(Some(xxs))
+                })
+                .map_err(|e| Error::Function(format!("计算恒星{star}位置错误:{e}")))?
+                .ok_or_else(|| Error::BadRequest(format!("星表中没有恒星:{star}")))?;
+            series.extend(xxs);
+        }
+        Ok(series)
+    }
+
+    /// 以序号遍历星表，计算其中全部恒星的位置
+    /// 星表只读取一次，序号超出星表时结束，其余错误直接返回
+    pub fn calc_stars(&self, jd_utc: f64) -> Result<Vec<(String, [f64; 6])>, Error> {
+        self.with_backend(jd_utc, |flags| {
+            let mut stars = vec![];
+            for n in 1.. {
+                match swe_fixstar2_ut(&n.to_string(), jd_utc, flags) {
+                    Ok(star) => stars.push(star),
+                    Err(e) if is_star_not_found(&e) => break,
+                    Err(e) => return Err(e),
+                }
+            }
+            Ok(stars)
+        })
+        .map_err(|e| Error::Function(format!("计算恒星位置错误:{e}")))
+    }
+
+    /// 计算时差，即真太阳时-平太阳时，单位：日
+    pub fn time_equ(&self, jd_utc: f64) -> Result<f64, Error> {
+        self.with_backend(jd_utc, |_| swe_time_equ(jd_utc))
+            .map_err(|e| Error::Function(format!("计算时差错误:{e}")))
+    }
+
+    /// 求jd_utc之后的下一次日食
+    /// 返回swe_sol_eclipse_when_glob()的计算结果：日食类型标志，及食甚、初亏、复圆等时刻
+    pub fn sol_eclipse_when_glob(&self, jd_utc: f64) -> Result<(i32, [f64; 10]), Error> {
+        self.with_backend(jd_utc, |flags| {
+            swe_sol_eclipse_when_glob(jd_utc, flags, 0, false)
+        })
+        .map_err(|e| Error::Function(format!("计算日食错误:{e}")))
+    }
+
+    /// 计算日食食甚时，中心线的地理位置及日食属性
+    /// 返回swe_sol_eclipse_where()的计算结果：日食类型标志、地理位置、食分及沙罗序列等
+    pub fn sol_eclipse_where(&self, jd_utc: f64) -> Result<(i32, [f64; 10], [f64; 20]), Error> {
+        self.with_backend(jd_utc, |flags| swe_sol_eclipse_where(jd_utc, flags))
+            .map_err(|e| Error::Function(format!("计算日食食甚位置错误:{e}")))
+    }
+
+    /// 求jd_utc之后，在观测者所在地可见的下一次日食
+    /// geopos: 观测者的地理经度、纬度、海拔
+    /// 返回swe_sol_eclipse_when_loc()的计算结果：日食类型及可见性标志，食甚及初亏、食既、生光、复圆的时刻，日食属性
+    pub fn sol_eclipse_when_loc(
+        &self,
+        jd_utc: f64,
+        geopos: [f64; 3],
+    ) -> Result<(i32, [f64; 10], [f64; 20]), Error> {
+        self.with_backend(jd_utc, |flags| {
+            swe_sol_eclipse_when_loc(jd_utc, flags, &geopos, false)
+        })
+        .map_err(|e| Error::Function(format!("计算当地日食错误:{e}")))
+    }
+
+    /// 计算观测者所在地的日食属性
+    /// geopos: 观测者的地理经度、纬度、海拔
+    /// 返回swe_sol_eclipse_how()的计算结果：日食类型标志、食分、食面积比例、太阳方位及高度等
+    pub fn sol_eclipse_how(
+        &self,
+        jd_utc: f64,
+        geopos: [f64; 3],
+    ) -> Result<(i32, [f64; 20]), Error> {
+        self.with_backend(jd_utc, |flags| swe_sol_eclipse_how(jd_utc, flags, &geopos))
+            .map_err(|e| Error::Function(format!("计算日食食分错误:{e}")))
+    }
+
+    /// 求jd_utc之后的下一次月食
+    /// 返回swe_lun_eclipse_when()的计算结果：月食类型标志，及食甚、初亏、复圆等时刻
+    pub fn lun_eclipse_when(&self, jd_utc: f64) -> Result<(i32, [f64; 10]), Error> {
+        self.with_backend(jd_utc, |flags| {
+            swe_lun_eclipse_when(jd_utc, flags, 0, false)
+        })
+        .map_err(|e| Error::Function(format!("计算月食错误:{e}")))
+    }
+
+    /// 计算月食属性
+    /// geopos: 观测者的地理经度、纬度、海拔
+    /// 返回swe_lun_eclipse_how()的计算结果：月食类型标志、本影食分、半影食分、月亮方位及高度、沙罗序列等
+    pub fn lun_eclipse_how(
+        &self,
+        jd_utc: f64,
+        geopos: [f64; 3],
+    ) -> Result<(i32, [f64; 20]), Error> {
+        self.with_backend(jd_utc, |flags| swe_lun_eclipse_how(jd_utc, flags, &geopos))
+            .map_err(|e| Error::Function(format!("计算月食食分错误:{e}")))
+    }
+
+    /// 计算岁差值，单位：度
+    pub fn ayanamsa(&self, jd_utc: f64) -> Result<f64, Error> {
+        self.with_backend(jd_utc, |_| Ok(swe_get_ayanamsa_ut(jd_utc)))
+            .map_err(|e| Error::Function(format!("计算岁差错误:{e}")))
+    }
+
+    /// 以配置的星历表计算，星历表文件不存在或计算失败，且允许回退时，改用Moshier星历表
+    /// 星历表文件不存在时，swe会静默改用Moshier星历表，故计算前先检查文件
+    fn with_backend<T>(
+        &self,
+        jd_utc: f64,
+        f: impl Fn(&[Flag]) -> Result<T, String>,
+    ) -> Result<T, String> {
+        let result = match self.missing_file(jd_utc) {
+            Some(file) => Err(format!(
+                "星历表文件{file}不在EPHE_PATH:{}中",
+                self.ephe_path
+            )),
+            None => self.compute(self.backend, &f),
+        };
+        match result {
+            Err(_) if self.fallback && self.backend != Backend::Moshier => {
+                let result = self.compute(Backend::Moshier, &f);
+                if result.is_ok() {
+                    self.used.set(Backend::Moshier);
+                }
+                result
+            }
+            _ => result,
+        }
+    }
+
+    fn compute<T>(
+        &self,
+        backend: Backend,
+        f: &impl Fn(&[Flag]) -> Result<T, String>,
+    ) -> Result<T, String> {
+        self.set_ephemeris(backend);
+        let mut flags = self.flags.clone();
+        flags.push(backend_flag(backend));
+        let result = f(&flags);
+        swe_close();
+        result
+    }
+
+    /// 计算jd_utc时所需，但EPHE_PATH中没有的星历表文件，文件都存在时为None
+    fn missing_file(&self, jd_utc: f64) -> Option<String> {
+        match self.backend {
+            Backend::Moshier => None,
+            Backend::Jpl => self
+                .jpl_file
+                .filter(|jpl_file| !self.find_file(jpl_file))
+                .map(|jpl_file| jpl_file.to_string()),
+            Backend::Swiss => {
+                // 同一年代的文件都存在时，不再检查
+                let block = swiss_file_block(jd_utc);
+                if self.files.get() == Some((block, true)) {
+                    return None;
+                }
+                let missing = swiss_files(block)
+                    .into_iter()
+                    .find(|file| !self.find_file(file));
+                self.files.set(Some((block, missing.is_none())));
+                missing
+            }
+        }
+    }
+
+    /// EPHE_PATH中是否有星历表文件
+    fn find_file(&self, file: &str) -> bool {
+        self.ephe_path
+            .split(PATH_SEPARATORS)
+            .any(|dir| Path::new(dir).join(file).is_file())
+    }
+
+    /// 设置星历表路径、观测者位置及岁差
+    fn set_ephemeris(&self, backend: Backend) {
+        swe_set_ephe_path(self.ephe_path);
+        if backend == Backend::Jpl {
+            if let Some(jpl_file) = self.jpl_file {
+                swe_set_jpl_file(jpl_file);
+            }
+        }
+        if let Some(observer) = &self.observer {
+            swe_set_topo(observer.long, observer.lat, observer.alt);
+        }
+        swe_set_sid_mode(self.sid_mode, 0.0, 0.0);
+    }
+}
+
+/// 星历表对应的计算标志
+fn backend_flag(backend: Backend) -> Flag {
+    match backend {
+        Backend::Swiss => Flag::SeflgSwieph,
+        Backend::Moshier => Flag::SeflgMoseph,
+        Backend::Jpl => Flag::SeflgJpleph,
+    }
+}
+
+/// swe_fixstar2_ut()的错误是否为星表中没有该恒星
+/// 名称不存在时为：star xxx not found，序号超出星表时为：sequential fixstar number n is not available
+/// 星表文件不存在时为：SwissEph file 'sefstars.txt' not found，不属此类
+fn is_star_not_found(e: &str) -> bool {
+    e.contains("is not available") || (e.contains("star ") && e.contains("not found"))
+}
+
+/// jd_utc所在的瑞士星历表文件的起始世纪，如：2024年为18，即1800-2399年
+fn swiss_file_block(jd_utc: f64) -> i32 {
+    let year = 2000.0 + (jd_utc - 2451545.0) / 365.25;
+    let century = (year / 100.0).floor() as i32;
+    century.div_euclid(SWISS_FILE_YEARS / 100) * (SWISS_FILE_YEARS / 100)
+}
+
+/// 瑞士星历表的行星、月亮文件名，如：sepl_18.se1、semom48.se1
+fn swiss_files(block: i32) -> [String; 2] {
+    let sign = if block < 0 { 'm' } else { '_' };
+    let block = block.abs();
+    [
+        format!("sepl{sign}{block:02}.se1"),
+        format!("semo{sign}{block:02}.se1"),
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::{json, Value};
+    use swe::{Body, Flag};
+
+    use super::{
+        backend_flag, is_star_not_found, swiss_file_block, swiss_files, Ephemeris, BACKEND_HEADER,
+    };
+    use crate::{
+        error::Error,
+        request::DateRangeRequest,
+        state::{AppState, Backend},
+        test_util::MOSHIER,
+    };
+
+    /// 2024-06-20的序列请求，options为其余字段
+    fn request(options: Value) -> DateRangeRequest {
+        let date =
+            json!({"year": 2024, "month": 6, "day": 20, "hour": 0, "minute": 0, "second": 0});
+        let mut r = json!({"start": date, "end": date});
+        r.as_object_mut()
+            .unwrap()
+            .extend(options.as_object().unwrap().clone());
+        serde_json::from_value(r).unwrap()
+    }
+
+    /// 各计算标志之和
+    fn flag_bits(ephemeris: &Ephemeris) -> i32 {
+        ephemeris
+            .flags
+            .clone()
+            .into_iter()
+            .fold(0, |bits, flag| bits | flag as i32)
+    }
+
+    #[test]
+    fn test_backend_flag() {
+        assert_eq!(
+            Flag::SeflgSwieph as i32,
+            backend_flag(Backend::Swiss) as i32
+        );
+        assert_eq!(
+            Flag::SeflgMoseph as i32,
+            backend_flag(Backend::Moshier) as i32
+        );
+        assert_eq!(Flag::SeflgJpleph as i32, backend_flag(Backend::Jpl) as i32);
+    }
+
+    #[test]
+    fn test_swiss_files() {
+        // 2000-01-01
+        assert_eq!(18, swiss_file_block(2451545.0));
+        // 1799-07-01
+        assert_eq!(12, swiss_file_block(2378313.5));
+        // -4800年
+        assert_eq!(-48, swiss_file_block(-32027.5));
+
+        assert_eq!(
+            ["sepl_18.se1".to_string(), "semo_18.se1".to_string()],
+            swiss_files(18)
+        );
+        assert_eq!(
+            ["seplm48.se1".to_string(), "semom48.se1".to_string()],
+            swiss_files(-48)
+        );
+    }
+
+    #[test]
+    fn test_fallback() {
+        // EPHE_PATH中没有星历表文件
+        let mut app_state = AppState {
+            ephe_path: "/nonexistent".into(),
+            backend: Backend::Swiss,
+            jpl_file: None,
+            fallback: true,
+        };
+        let ephemeris = Ephemeris::with_flags(&app_state, vec![]);
+        assert_eq!(Backend::Swiss, ephemeris.backend());
+        assert!(ephemeris.calc(2451545.0, &Body::SeSun).is_ok());
+        assert_eq!(Backend::Moshier, ephemeris.backend());
+        assert_eq!(
+            (BACKEND_HEADER, "moshier".to_string()),
+            ephemeris.backend_header()
+        );
+
+        // 不允许回退时，返回错误
+        app_state.fallback = false;
+        let ephemeris = Ephemeris::with_flags(&app_state, vec![]);
+        assert!(matches!(
+            ephemeris.calc(2451545.0, &Body::SeSun),
+            Err(Error::Function(_))
+        ));
+        assert_eq!(Backend::Swiss, ephemeris.backend());
+
+        // JPL星历表文件不存在
+        let app_state = AppState {
+            ephe_path: "/nonexistent".into(),
+            backend: Backend::Jpl,
+            jpl_file: Some("de441.eph".into()),
+            fallback: false,
+        };
+        let ephemeris = Ephemeris::with_flags(&app_state, vec![]);
+        assert_eq!(
+            Some("de441.eph".to_string()),
+            ephemeris.missing_file(2451545.0)
+        );
+    }
+
+    #[test]
+    fn test_is_star_not_found() {
+        assert!(is_star_not_found("star Foo not found"));
+        assert!(is_star_not_found(
+            "error, swe_fixstar(): sequential fixstar number 1200 is not available"
+        ));
+        // 星表文件不存在
+        assert!(!is_star_not_found(
+            "SwissEph file 'sefstars.txt' not found in PATH '/tmp/swe'"
+        ));
+    }
+
+    #[test]
+    fn test_new_flags() {
+        let ephemeris = Ephemeris::new(&MOSHIER, &request(json!({}))).unwrap();
+        assert!(ephemeris.flags.is_empty());
+        assert!(ephemeris.observer.is_none());
+
+        let r = request(json!({
+            "output": "full",
+            "coordinate": "equatorial_j2000",
+            "observer": {"lat": 39.9, "long": 116.4},
+            "position": {"no_aberration": true, "no_nutation": true, "j2000": true}
+        }));
+        let ephemeris = Ephemeris::new(&MOSHIER, &r).unwrap();
+        // J2000只设置一次
+        assert_eq!(6, ephemeris.flags.len());
+        assert_eq!(
+            Flag::SeflgSpeed as i32
+                | Flag::SeflgEquatorial as i32
+                | Flag::SeflgJ2000 as i32
+                | Flag::SeflgTopoctr as i32
+                | Flag::SeflgNoaberr as i32
+                | Flag::SeflgNonut as i32,
+            flag_bits(&ephemeris)
+        );
+        assert!(ephemeris.observer.is_some());
+
+        let r = request(json!({
+            "frame": "barycentric",
+            "zodiac": "sidereal",
+            "ayanamsa": "krishnamurti",
+            "position": {"no_light_time": true, "no_deflection": true, "icrs": true}
+        }));
+        let ephemeris = Ephemeris::new(&MOSHIER, &r).unwrap();
+        assert_eq!(
+            Flag::SeflgBaryctr as i32
+                | Flag::SeflgSidereal as i32
+                | Flag::SeflgTruepos as i32
+                | Flag::SeflgNogdefl as i32
+                | Flag::SeflgIcrs as i32,
+            flag_bits(&ephemeris)
+        );
+        // SE_SIDM_KRISHNAMURTI
+        assert_eq!(5, ephemeris.sid_mode);
+    }
+
+    #[test]
+    fn test_new_rejects_observer_with_frame() {
+        for frame in ["heliocentric", "barycentric"] {
+            let r = request(json!({"frame": frame, "observer": {"lat": 39.9, "long": 116.4}}));
+            assert!(matches!(
+                Ephemeris::new(&MOSHIER, &r),
+                Err(Error::BadRequest(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_new_rejects_sidereal_equatorial() {
+        for coordinate in ["equatorial", "equatorial_j2000"] {
+            let r = request(json!({"zodiac": "sidereal", "coordinate": coordinate}));
+            assert!(matches!(
+                Ephemeris::new(&MOSHIER, &r),
+                Err(Error::BadRequest(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_new_rejects_sidereal_j2000() {
+        let r = request(json!({"zodiac": "sidereal", "position": {"j2000": true}}));
+        assert!(matches!(
+            Ephemeris::new(&MOSHIER, &r),
+            Err(Error::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_sun_declination_at_solstice() {
+        let r = request(json!({"coordinate": "equatorial"}));
+        let ephemeris = Ephemeris::new(&MOSHIER, &r).unwrap();
+
+        // 2024-06-20 20:51 UTC夏至，太阳赤纬约为黄赤交角23.44度
+        let xx = ephemeris.calc(2460482.36875, &Body::SeSun).unwrap();
+        assert!((xx[1] - 23.44).abs() < 0.01, "{}", xx[1]);
+    }
+
+    #[test]
+    fn test_no_light_time() {
+        let jd = 2460482.36875;
+        let apparent = Ephemeris::new(&MOSHIER, &request(json!({})))
+            .unwrap()
+            .calc(jd, &Body::SeSun)
+            .unwrap();
+        let r = request(json!({"position": {"no_light_time": true}}));
+        let geometric = Ephemeris::new(&MOSHIER, &r)
+            .unwrap()
+            .calc(jd, &Body::SeSun)
+            .unwrap();
+
+        // 太阳几何位置比视位置超前约20.5角秒，即光行差
+        let d = (geometric[0] - apparent[0]) * 3600.0;
+        assert!((d - 20.5).abs() < 1.0, "{}", d);
+
+        // 几何位置已不计光行差，no_aberration不再改变结果
+        let r = request(json!({"position": {"no_light_time": true, "no_aberration": true}}));
+        let xx = Ephemeris::new(&MOSHIER, &r)
+            .unwrap()
+            .calc(jd, &Body::SeSun)
+            .unwrap();
+        assert_eq!(geometric[0], xx[0]);
+    }
+
+    #[test]
+    fn test_lahiri_ayanamsa() {
+        let r = request(json!({"zodiac": "sidereal", "ayanamsa": "lahiri"}));
+        let ephemeris = Ephemeris::new(&MOSHIER, &r).unwrap();
+
+        // J2000时，Lahiri岁差约为23°51′
+        let ayanamsa = ephemeris.ayanamsa(2451545.0).unwrap();
+        assert!((ayanamsa - 23.85).abs() < 0.01, "{ayanamsa}");
+
+        // 恒星黄道经度为回归黄道经度减去岁差
+        let sidereal = ephemeris.calc(2451545.0, &Body::SeSun).unwrap()[0];
+        let tropical = Ephemeris::new(&MOSHIER, &request(json!({})))
+            .unwrap()
+            .calc(2451545.0, &Body::SeSun)
+            .unwrap()[0];
+        assert!((tropical - sidereal - ayanamsa).abs() < 0.01);
+    }
+}
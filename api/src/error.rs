@@ -21,6 +21,7 @@ impl Display for DateTimeError {
 #[derive(Debug)]
 pub enum Error {
     DateTime(DateTimeError),
+    BadRequest(String),
     Function(String),
 }
 
@@ -37,6 +38,7 @@ impl Display for Error {
                 DateTimeError::InvalidDateTime(s) => s,
                 DateTimeError::InvalidZone(s) => s,
             },
+            Error::BadRequest(s) => s,
             Error::Function(s) => s,
         };
         write!(f, "{}", s)
@@ -47,6 +49,7 @@ impl ResponseError for Error {
     fn status_code(&self) -> StatusCode {
         match self {
             Error::DateTime(_) => StatusCode::BAD_REQUEST,
+            Error::BadRequest(_) => StatusCode::BAD_REQUEST,
             Error::Function(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
@@ -0,0 +1,252 @@
+use crate::{
+    ephemeris::Ephemeris,
+    error::Error,
+    horo_date_time::HoroDateTime,
+    solar_term::{solar_terms, SolarTerm},
+};
+
+/// 天干
+pub const STEMS: [&str; 10] = ["甲", "乙", "丙", "丁", "戊", "己", "庚", "辛", "壬", "癸"];
+
+/// 地支
+pub const BRANCHES: [&str; 12] = [
+    "子", "丑", "寅", "卯", "辰", "巳", "午", "未", "申", "酉", "戌", "亥",
+];
+
+/// 立春的节气序号
+const LI_CHUN: usize = 21;
+
+/// 干支
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Pillar {
+    /// 天干序号，0为甲
+    pub stem: usize,
+    /// 地支序号，0为子
+    pub branch: usize,
+}
+
+impl Pillar {
+    /// 由六十甲子序号构造，0为甲子
+    pub fn from_index(index: i64) -> Self {
+        let index = index.rem_euclid(60) as usize;
+        Self {
+            stem: index % 10,
+            branch: index % 12,
+        }
+    }
+
+    /// 干支名，如：甲子
+    pub fn name(&self) -> String {
+        format!("{}{}", STEMS[self.stem], BRANCHES[self.branch])
+    }
+}
+
+/// 四柱
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct FourPillars {
+    pub year: Pillar,
+    pub month: Pillar,
+    pub day: Pillar,
+    pub hour: Pillar,
+    /// 本月起始的节
+    pub month_start: SolarTerm,
+    /// 下月起始的节
+    pub month_end: SolarTerm,
+}
+
+/// 年柱，year为以立春为岁首的年
+pub fn year_pillar(year: i32) -> Pillar {
+    // 公元4年为甲子年
+    Pillar::from_index(i64::from(year) - 4)
+}
+
+/// 月柱，month为以寅月为0的月序号
+pub fn month_pillar(year_stem: usize, month: usize) -> Pillar {
+    // 五虎遁：甲己之年丙作首
+    Pillar {
+        stem: (year_stem % 5 * 2 + 2 + month) % 10,
+        branch: (month + 2) % 12,
+    }
+}
+
+/// 日柱，day为当地日期的儒略日数，即当地该日12:00的儒略日
+pub fn day_pillar(day: i64) -> Pillar {
+    // 儒略日数2451545（2000-01-01）为戊午日
+    Pillar::from_index(day + 49)
+}
+
+/// 时柱，hour为当地时间的时
+pub fn hour_pillar(day_stem: usize, hour: u8) -> Pillar {
+    let branch = usize::from(hour).div_ceil(2) % 12;
+    // 五鼠遁：甲己还加甲
+    Pillar {
+        stem: (day_stem % 5 * 2 + branch) % 10,
+        branch,
+    }
+}
+
+/// 四柱
+/// 年柱以立春为界，月柱以节为界，日柱以当地23时为界（子初换日），时柱以时辰为界
+/// date.jd_utc为实际时刻，用于与节比较；date的日期时间为钟表时间或真太阳时，用于日柱、时柱
+pub fn four_pillars(ephemeris: &Ephemeris, date: &HoroDateTime) -> Result<FourPillars, Error> {
+    // 相邻两节间隔约30日
+    let terms: Vec<SolarTerm> = solar_terms(ephemeris, date.jd_utc - 32.0, date.jd_utc + 32.0)?
+        .into_iter()
+        .filter(|term| !term.is_zhong_qi())
+        .collect();
+    let next = terms
+        .iter()
+        .position(|term| term.jd_utc > date.jd_utc)
+        .filter(|&i| i > 0)
+        .ok_or_else(|| Error::Function("计算节气错误".to_string()))?;
+    let month_start = terms[next - 1];
+    let month_end = terms[next];
+
+    // 以寅月为0的月序号
+    let month = (month_start.index + 24 - LI_CHUN) % 24 / 2;
+
+    // 子月、丑月在公历1、2月时，尚未立春，属于上一年
+    let year = if date.month <= 2 && month >= 10 {
+        date.year - 1
+    } else {
+        date.year
+    };
+    let year = year_pillar(year);
+    let month = month_pillar(year.stem, month);
+
+    let local_day = (date.jd_utc + date.tz / 24.0 + 0.5).floor() as i64;
+    // 子初换日：23时起为次日
+    let local_day = if date.hour == 23 {
+        local_day + 1
+    } else {
+        local_day
+    };
+    let day = day_pillar(local_day);
+    let hour = hour_pillar(day.stem, date.hour);
+
+    Ok(FourPillars {
+        year,
+        month,
+        day,
+        hour,
+        month_start,
+        month_end,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{day_pillar, four_pillars, hour_pillar, month_pillar, year_pillar};
+    use crate::{horo_date_time::horo_date_time, solar_time::solar_time, test_util::moshier};
+
+    #[test]
+    fn test_year_pillar() {
+        assert_eq!("甲子", year_pillar(1984).name());
+        assert_eq!("庚辰", year_pillar(2000).name());
+        assert_eq!("癸卯", year_pillar(2023).name());
+    }
+
+    #[test]
+    fn test_month_pillar() {
+        // 甲年寅月为丙寅
+        assert_eq!("丙寅", month_pillar(0, 0).name());
+        // 乙年寅月为戊寅
+        assert_eq!("戊寅", month_pillar(1, 0).name());
+        // 癸年丑月为乙丑
+        assert_eq!("乙丑", month_pillar(9, 11).name());
+    }
+
+    #[test]
+    fn test_day_pillar() {
+        // 2000-01-01 戊午
+        assert_eq!("戊午", day_pillar(2451545).name());
+        // 2023-01-22 庚辰
+        assert_eq!("庚辰", day_pillar(2459967).name());
+    }
+
+    #[test]
+    fn test_hour_pillar() {
+        // 甲日子时为甲子
+        assert_eq!("甲子", hour_pillar(0, 0).name());
+        assert_eq!("甲子", hour_pillar(0, 23).name());
+        // 乙日丑时为丁丑
+        assert_eq!("丁丑", hour_pillar(1, 1).name());
+        // 戊日午时为戊午
+        assert_eq!("戊午", hour_pillar(4, 12).name());
+    }
+
+    #[test]
+    fn test_four_pillars_true_solar_time() {
+        let ephemeris = moshier(vec![]);
+
+        // 2024-02-04 16:26:53 立春，立春后约1分钟，东经120度
+        // 时差约-14分钟，真太阳时约为16:14，但仍在立春之后
+        let date = horo_date_time(2024, 2, 4, 16, 27, 53, 8.0, false).unwrap();
+        let true_solar_time = solar_time(&ephemeris, date.jd_utc, 120.0)
+            .unwrap()
+            .true_solar_time;
+        assert_eq!(date.jd_utc, true_solar_time.jd_utc);
+        assert_eq!(16, true_solar_time.hour);
+
+        let pillars = four_pillars(&ephemeris, &true_solar_time).unwrap();
+        assert_eq!("甲辰", pillars.year.name());
+        assert_eq!("丙寅", pillars.month.name());
+        assert_eq!("戊戌", pillars.day.name());
+        assert_eq!("庚申", pillars.hour.name());
+    }
+
+    #[test]
+    fn test_four_pillars_li_chun() {
+        let ephemeris = moshier(vec![]);
+
+        // 2024-02-04 16:26:53 立春
+        // 立春前1分钟，仍为癸卯年丑月
+        let date = horo_date_time(2024, 2, 4, 16, 25, 53, 8.0, false).unwrap();
+        let pillars = four_pillars(&ephemeris, &date).unwrap();
+        assert_eq!("癸卯", pillars.year.name());
+        assert_eq!("乙丑", pillars.month.name());
+        assert_eq!(21, pillars.month_end.index);
+
+        // 立春后1分钟，为甲辰年寅月
+        let date = horo_date_time(2024, 2, 4, 16, 27, 53, 8.0, false).unwrap();
+        let pillars = four_pillars(&ephemeris, &date).unwrap();
+        assert_eq!("甲辰", pillars.year.name());
+        assert_eq!("丙寅", pillars.month.name());
+        assert_eq!(21, pillars.month_start.index);
+        // 日柱不随立春改变
+        assert_eq!("戊戌", pillars.day.name());
+    }
+
+    #[test]
+    fn test_four_pillars_jie() {
+        let ephemeris = moshier(vec![]);
+
+        // 2024-03-05 10:22:31 惊蛰
+        let date = horo_date_time(2024, 3, 5, 10, 21, 31, 8.0, false).unwrap();
+        let pillars = four_pillars(&ephemeris, &date).unwrap();
+        assert_eq!("甲辰", pillars.year.name());
+        assert_eq!("丙寅", pillars.month.name());
+
+        let date = horo_date_time(2024, 3, 5, 10, 23, 31, 8.0, false).unwrap();
+        let pillars = four_pillars(&ephemeris, &date).unwrap();
+        assert_eq!("甲辰", pillars.year.name());
+        assert_eq!("丁卯", pillars.month.name());
+    }
+
+    #[test]
+    fn test_four_pillars_zi_chu() {
+        let ephemeris = moshier(vec![]);
+
+        // 2024-02-04为戊戌日，22:59仍为戊戌日亥时
+        let date = horo_date_time(2024, 2, 4, 22, 59, 0, 8.0, false).unwrap();
+        let pillars = four_pillars(&ephemeris, &date).unwrap();
+        assert_eq!("戊戌", pillars.day.name());
+        assert_eq!("癸亥", pillars.hour.name());
+
+        // 23:00起为己亥日子时
+        let date = horo_date_time(2024, 2, 4, 23, 0, 0, 8.0, false).unwrap();
+        let pillars = four_pillars(&ephemeris, &date).unwrap();
+        assert_eq!("己亥", pillars.day.name());
+        assert_eq!("甲子", pillars.hour.name());
+    }
+}
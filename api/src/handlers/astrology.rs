@@ -0,0 +1,182 @@
+use actix_web::{post, web, Responder};
+use swe::Flag;
+
+use crate::{
+    aspect::{aspect_samples, aspects, normalize_aspect},
+    body::body_from_name,
+    ephemeris::Ephemeris,
+    error::Error,
+    ingress::{ingresses, void_of_course},
+    request::{AspectRequest, EventRequest},
+    response::{
+        AspectResponser, AspectSampleResponser, AspectsResponser, IngressResponser,
+        StationResponser, VoidOfCourseResponser,
+    },
+    state::AppState,
+    station::stations,
+};
+
+use super::{event_range, json_response};
+
+/// 星体入宫
+/// 求区间内星体越过黄道十二宫宫界（黄经30度整数倍）的精确时刻
+/// name: 同/body/{name}
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="入宫",
+    context_path="/api",
+    params(
+        ("name" = String, Path, description = "星体名称，如：moon、mercury、true_node"),
+    ),
+    request_body=EventRequest,
+    responses(
+        (status = 200, description = "OK", body = Vec<IngressResponser>),
+        (status = 400, description = "不支持的星体"),
+    ),
+)
+)]
+#[post("/ingresses/{name}")]
+pub async fn ingress_events(
+    app_state: web::Data<AppState>,
+    name: web::Path<String>,
+    r: actix_web_validator::Json<EventRequest>,
+) -> Result<impl Responder, Error> {
+    let body = body_from_name(&name)?;
+    let (start, end) = event_range(&r)?;
+    let ephemeris = Ephemeris::with_flags(&app_state, vec![]);
+
+    let ingresses = ingresses(&ephemeris, &body, start.jd_utc, end.jd_utc)?
+        .iter()
+        .map(|ingress| IngressResponser::new(ingress, r.tz))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(json_response(&ephemeris, ingresses))
+}
+
+/// 月亮空亡
+/// 月亮自与太阳至冥王星的最后一个主要相位（合、六合、刑、拱、冲）起，至入下一宫止
+/// 给出与区间相交的全部空亡
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="入宫",
+    context_path="/api",
+    request_body=EventRequest,
+    responses(
+        (status = 200, description = "OK", body = Vec<VoidOfCourseResponser>),
+    ),
+)
+)]
+#[post("/void_of_course")]
+pub async fn void_of_course_events(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<EventRequest>,
+) -> Result<impl Responder, Error> {
+    let (start, end) = event_range(&r)?;
+    let ephemeris = Ephemeris::with_flags(&app_state, vec![]);
+
+    let voids = void_of_course(&ephemeris, start.jd_utc, end.jd_utc)?
+        .iter()
+        .map(|void| VoidOfCourseResponser::new(void, r.tz))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(json_response(&ephemeris, voids))
+}
+
+/// 行星的留
+/// 求区间内水星至冥王星经度速度变号（顺行转逆行、逆行转顺行）的精确时刻及黄经
+/// 并给出影子期：前影子期自行星首次到达逆行转顺行的黄经起，至顺行转逆行止
+/// 后影子期自逆行转顺行起，至行星回到顺行转逆行的黄经止
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="留",
+    context_path="/api",
+    request_body=EventRequest,
+    responses(
+        (status = 200, description = "OK", body = Vec<StationResponser>),
+    ),
+)
+)]
+#[post("/stations")]
+pub async fn station_events(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<EventRequest>,
+) -> Result<impl Responder, Error> {
+    let (start, end) = event_range(&r)?;
+    let ephemeris = Ephemeris::with_flags(&app_state, vec![Flag::SeflgSpeed]);
+
+    let stations = stations(&ephemeris, start.jd_utc, end.jd_utc)?
+        .iter()
+        .map(|station| StationResponser::new(station, r.tz))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(json_response(&ephemeris, stations))
+}
+
+/// 两星体的相位
+/// 求区间内两星体黄经之差等于给定相位的精确时刻
+/// 并按步长（区间小于1天为1小时，否则为1天）给出在容许度内的相位、与精确相位之差，及入相位或出相位
+/// 相位归算到[0, 180]，如270度即90度
+/// body1、body2: 同/body/{name}
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="相位",
+    context_path="/api",
+    params(
+        ("body1" = String, Path, description = "星体名称，如：jupiter"),
+        ("body2" = String, Path, description = "星体名称，如：saturn"),
+    ),
+    request_body=AspectRequest,
+    responses(
+        (status = 200, description = "OK", body = AspectsResponser),
+        (status = 400, description = "不支持的星体"),
+    ),
+)
+)]
+#[post("/aspects/{body1}/{body2}")]
+pub async fn aspect_events(
+    app_state: web::Data<AppState>,
+    names: web::Path<(String, String)>,
+    r: actix_web_validator::Json<AspectRequest>,
+) -> Result<impl Responder, Error> {
+    let body1 = body_from_name(&names.0)?;
+    let body2 = body_from_name(&names.1)?;
+    let (start, end) = event_range(&r.range)?;
+    let ephemeris = Ephemeris::with_flags(&app_state, vec![]);
+
+    let mut angles: Vec<f64> = r
+        .angles
+        .iter()
+        .map(|&angle| normalize_aspect(angle))
+        .collect();
+    angles.sort_by(f64::total_cmp);
+    angles.dedup();
+
+    let exact = aspects(
+        &ephemeris,
+        &body1,
+        &body2,
+        &angles,
+        start.jd_utc,
+        end.jd_utc,
+    )?
+    .iter()
+    .map(|aspect| AspectResponser::new(aspect, r.range.tz))
+    .collect::<Result<Vec<_>, Error>>()?;
+    let samples = aspect_samples(
+        &ephemeris,
+        &body1,
+        &body2,
+        &angles,
+        r.orb,
+        start.jd_utc,
+        end.jd_utc,
+    )?
+    .iter()
+    .map(|sample| AspectSampleResponser::new(sample, r.range.tz))
+    .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(json_response(
+        &ephemeris,
+        AspectsResponser::new(exact, samples),
+    ))
+}
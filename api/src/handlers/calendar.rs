@@ -0,0 +1,200 @@
+use actix_web::{post, web, Responder};
+
+use crate::{
+    ephemeris::Ephemeris,
+    error::{DateTimeError, Error},
+    ganzhi::four_pillars,
+    horo_date_time::HoroDateTime,
+    lunar_calendar::{from_lunar, to_lunar},
+    request::{DateTimeRequest, EventRequest, LunarDateRequest, YearRangeRequest},
+    response::{
+        FourPillarsResponser, LunarDateResponser, SeasonsResponser, SolarTermResponser,
+        SolarTimeConversionResponser,
+    },
+    solar_term::{seasons, solar_terms},
+    solar_time::solar_time,
+    state::AppState,
+};
+
+use super::{event_range, horo_date, json_response};
+
+/// 二十四节气
+/// 以/sun的太阳黄经求根，得到区间内太阳黄经为15度整数倍的精确时刻
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="节气",
+    context_path="/api",
+    request_body=EventRequest,
+    responses(
+        (status = 200, description = "OK", body = Vec<SolarTermResponser>),
+    ),
+)
+)]
+#[post("/solar_terms")]
+pub async fn solar_term_events(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<EventRequest>,
+) -> Result<impl Responder, Error> {
+    let (start, end) = event_range(&r)?;
+    let ephemeris = Ephemeris::with_flags(&app_state, vec![]);
+
+    let terms = solar_terms(&ephemeris, start.jd_utc, end.jd_utc)?
+        .iter()
+        .map(|term| SolarTermResponser::new(term, r.tz))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(json_response(&ephemeris, terms))
+}
+
+/// 公历转农历
+/// 以东8区的朔、中气按时宪历规则计算
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="农历",
+    context_path="/api",
+    request_body=DateTimeRequest,
+    responses(
+        (status = 200, description = "OK", body = LunarDateResponser),
+    ),
+)
+)]
+#[post("/lunar_calendar/to_lunar")]
+pub async fn gregorian_to_lunar(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<DateTimeRequest>,
+) -> Result<impl Responder, Error> {
+    let date = horo_date(&r.date, r.tz)?;
+    let ephemeris = Ephemeris::with_flags(&app_state, vec![]);
+
+    let lunar = to_lunar(&ephemeris, date.jd_utc)?;
+
+    Ok(json_response(
+        &ephemeris,
+        LunarDateResponser::new(&lunar, date),
+    ))
+}
+
+/// 农历转公历
+/// 返回农历该日东8区0时的公历时间，以请求的时区表示
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="农历",
+    context_path="/api",
+    request_body=LunarDateRequest,
+    responses(
+        (status = 200, description = "OK", body = LunarDateResponser),
+    ),
+)
+)]
+#[post("/lunar_calendar/from_lunar")]
+pub async fn lunar_to_gregorian(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<LunarDateRequest>,
+) -> Result<impl Responder, Error> {
+    let ephemeris = Ephemeris::with_flags(&app_state, vec![]);
+
+    let (lunar, jd_utc) = from_lunar(&ephemeris, r.year, r.month, r.day, r.leap)?;
+    let date = HoroDateTime::from_jd_zone(jd_utc, r.tz)?;
+
+    Ok(json_response(
+        &ephemeris,
+        LunarDateResponser::new(&lunar, date),
+    ))
+}
+
+/// 干支四柱
+/// 年柱以立春为界，月柱以节为界，时柱以时辰为界
+/// 日柱以当地23时为界，即子初换日：23:00-23:59属于次日的子时，日柱、时柱均按次日计算
+/// 给出long时，以真太阳时计算日柱、时柱，年柱、月柱仍以实际时刻与节比较
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="干支",
+    context_path="/api",
+    request_body=DateTimeRequest,
+    responses(
+        (status = 200, description = "OK", body = FourPillarsResponser),
+    ),
+)
+)]
+#[post("/ganzhi")]
+pub async fn ganzhi(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<DateTimeRequest>,
+) -> Result<impl Responder, Error> {
+    let date = horo_date(&r.date, r.tz)?;
+    let ephemeris = Ephemeris::with_flags(&app_state, vec![]);
+
+    let date = match r.long {
+        Some(long) => solar_time(&ephemeris, date.jd_utc, long)?.true_solar_time,
+        None => date,
+    };
+    let pillars = four_pillars(&ephemeris, &date)?;
+
+    Ok(json_response(
+        &ephemeris,
+        FourPillarsResponser::new(&pillars, date, r.tz)?,
+    ))
+}
+
+/// 地方平太阳时、真太阳时
+/// 由钟表时间及地理经度计算，时差由星历表计算
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="真太阳时",
+    context_path="/api",
+    request_body=DateTimeRequest,
+    responses(
+        (status = 200, description = "OK", body = SolarTimeConversionResponser),
+        (status = 400, description = "没有给出地理经度"),
+    ),
+)
+)]
+#[post("/solar_time")]
+pub async fn solar_time_conversion(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<DateTimeRequest>,
+) -> Result<impl Responder, Error> {
+    let long = r
+        .long
+        .ok_or_else(|| Error::BadRequest("计算真太阳时需要地理经度long".to_string()))?;
+    let date = horo_date(&r.date, r.tz)?;
+    let ephemeris = Ephemeris::with_flags(&app_state, vec![]);
+
+    let solar_time = solar_time(&ephemeris, date.jd_utc, long)?;
+
+    Ok(json_response(
+        &ephemeris,
+        SolarTimeConversionResponser::new(date, long, solar_time),
+    ))
+}
+
+/// 二分二至
+/// 求start_year至end_year各年春分、夏至、秋分、冬至（太阳黄经0、90、180、270度）的精确时刻，及四季长度
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="节气",
+    context_path="/api",
+    request_body=YearRangeRequest,
+    responses(
+        (status = 200, description = "OK", body = Vec<SeasonsResponser>),
+    ),
+)
+)]
+#[post("/seasons")]
+pub async fn season_events(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<YearRangeRequest>,
+) -> Result<impl Responder, Error> {
+    if r.end_year < r.start_year {
+        let err = DateTimeError::InvalidDateTime("start_year 必需不大于 end_year".to_string());
+        return Err(err.into());
+    }
+    let ephemeris = Ephemeris::with_flags(&app_state, vec![]);
+
+    let seasons = seasons(&ephemeris, r.start_year, r.end_year)?
+        .iter()
+        .map(|seasons| SeasonsResponser::new(seasons, r.tz))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(json_response(&ephemeris, seasons))
+}
@@ -0,0 +1,71 @@
+use actix_web::{post, web, Responder};
+
+use crate::{
+    eclipse::{eclipses, local_eclipse},
+    ephemeris::Ephemeris,
+    error::Error,
+    request::{EventRequest, LocalEclipseRequest},
+    response::{EclipseResponser, LocalEclipseResponser},
+    state::AppState,
+};
+
+use super::{event_range, horo_date, json_response};
+
+/// 日食、月食
+/// 求区间内的全部日食、月食，给出类型、食甚时刻、食分及沙罗序列
+/// 日食食分为食甚时中心线上月亮遮住太阳直径的比例，月食为本影食分，半影月食为半影食分
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="日月食",
+    context_path="/api",
+    request_body=EventRequest,
+    responses(
+        (status = 200, description = "OK", body = Vec<EclipseResponser>),
+    ),
+)
+)]
+#[post("/eclipses")]
+pub async fn eclipse_events(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<EventRequest>,
+) -> Result<impl Responder, Error> {
+    let (start, end) = event_range(&r)?;
+    let ephemeris = Ephemeris::with_flags(&app_state, vec![]);
+
+    let eclipses = eclipses(&ephemeris, start.jd_utc, end.jd_utc)?
+        .iter()
+        .map(|eclipse| EclipseResponser::new(eclipse, r.tz))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(json_response(&ephemeris, eclipses))
+}
+
+/// 观测者所在地的日食、月食
+/// 自date起查找下一次日食或月食，给出当地所见的食的类型、食分，及食甚、初亏、复圆等时刻太阳或月亮的高度
+/// 日食在当地不可见时，仅给出全球的交食
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="日月食",
+    context_path="/api",
+    request_body=LocalEclipseRequest,
+    responses(
+        (status = 200, description = "OK", body = LocalEclipseResponser),
+    ),
+)
+)]
+#[post("/eclipses/local")]
+pub async fn local_eclipse_circumstances(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<LocalEclipseRequest>,
+) -> Result<impl Responder, Error> {
+    let date = horo_date(&r.date, r.tz)?;
+    let ephemeris = Ephemeris::with_flags(&app_state, vec![]);
+
+    let geopos = [r.observer.long, r.observer.lat, r.observer.alt];
+    let local = local_eclipse(&ephemeris, r.kind, date.jd_utc, geopos)?;
+
+    Ok(json_response(
+        &ephemeris,
+        LocalEclipseResponser::new(&local, r.tz)?,
+    ))
+}
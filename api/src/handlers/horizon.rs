@@ -0,0 +1,96 @@
+use actix_web::{post, web, Responder};
+
+use crate::{
+    body::{body_name, rising_body_from_name},
+    ephemeris::Ephemeris,
+    error::Error,
+    request::{Polar, RiseSetRequest, TwilightRequest},
+    response::{DailyTwilightResponser, RiseSetResponser, TwilightResponser},
+    rise_set::{rise_set, twilights, Horizon, HorizonStatus},
+    state::AppState,
+};
+
+use super::{json_response, local_days};
+
+/// 升落
+/// 逐日计算观测者所在地星体的升、上中天、落，默认为太阳、月亮
+/// 可选是否计大气折射，及以日面、月面的上缘、中心或下缘为准
+/// 极昼、极夜等不升不落之日，标记为always_up或always_down，或按polar略去
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="升落",
+    context_path="/api",
+    request_body=RiseSetRequest,
+    responses(
+        (status = 200, description = "OK", body = Vec<RiseSetResponser>),
+        (status = 400, description = "不支持或没有升落的星体"),
+    ),
+)
+)]
+#[post("/rise_set")]
+pub async fn rise_set_events(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<RiseSetRequest>,
+) -> Result<impl Responder, Error> {
+    let bodies = r
+        .bodies
+        .iter()
+        .map(|name| rising_body_from_name(name))
+        .collect::<Result<Vec<_>, Error>>()?;
+    let days = local_days(&r.range)?;
+    let horizon = Horizon::new(&r.observer, 0.0, r.refraction, r.disc);
+    let ephemeris = Ephemeris::with_flags(&app_state, vec![]);
+
+    let mut events = vec![];
+    for day in days {
+        for body in &bodies {
+            let rise_set = rise_set(&ephemeris, body, &horizon, day.jd_utc)?;
+            if r.polar == Polar::Skip && rise_set.status != HorizonStatus::Normal {
+                continue;
+            }
+            events.push(RiseSetResponser::new(
+                body_name(body),
+                day.clone(),
+                &rise_set,
+                r.range.tz,
+            )?);
+        }
+    }
+
+    Ok(json_response(&ephemeris, events))
+}
+
+/// 晨昏蒙影
+/// 逐日计算观测者所在地民用（-6度）、航海（-12度）、天文（-18度）及自定义太阳高度的晨光始、昏影终
+/// 以太阳中心为准，不计大气折射
+/// 高纬度地区晨昏蒙影终夜不止时，status为always_up
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="升落",
+    context_path="/api",
+    request_body=TwilightRequest,
+    responses(
+        (status = 200, description = "OK", body = Vec<DailyTwilightResponser>),
+        (status = 400, description = "太阳高度超出范围"),
+    ),
+)
+)]
+#[post("/twilight")]
+pub async fn twilight_events(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<TwilightRequest>,
+) -> Result<impl Responder, Error> {
+    let days = local_days(&r.range)?;
+    let ephemeris = Ephemeris::with_flags(&app_state, vec![]);
+
+    let mut events = vec![];
+    for day in days {
+        let twilights = twilights(&ephemeris, &r.observer, &r.altitudes, day.jd_utc)?
+            .iter()
+            .map(|twilight| TwilightResponser::new(twilight, r.range.tz))
+            .collect::<Result<Vec<_>, Error>>()?;
+        events.push(DailyTwilightResponser::new(day, twilights));
+    }
+
+    Ok(json_response(&ephemeris, events))
+}
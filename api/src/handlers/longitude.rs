@@ -0,0 +1,394 @@
+use actix_web::{post, web, Responder};
+use swe::{swe_degnorm, Body};
+
+use crate::{
+    body::body_from_name,
+    ephemeris::Ephemeris,
+    error::Error,
+    horo_date_time::HoroDateTime,
+    request::{Coordinate, DateRangeRequest, Frame, Output, StarsRequest},
+    response::{LongResponser, SolarTimeResponser, StarResponser},
+    solar_time::solar_time,
+    state::AppState,
+};
+
+use super::{date_range, horo_date, json_response};
+
+/// 太阳的黄道经度
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="太阳黄道经度",
+    context_path="/api",
+    request_body=DateRangeRequest,
+    responses(
+        (status = 200, description = "OK", body = Vec<LongResponser>),
+    ),
+)
+)]
+#[post("/sun")]
+pub async fn sun_long(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<DateRangeRequest>,
+) -> Result<impl Responder, Error> {
+    let ephemeris = Ephemeris::new(&app_state, &r)?;
+    let longs = body_longs(&ephemeris, &r, &Body::SeSun)?;
+
+    Ok(json_response(&ephemeris, longs))
+}
+
+/// 月亮的黄道经度
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="月亮黄道经度",
+    context_path="/api",
+    request_body=DateRangeRequest,
+    responses(
+        (status = 200, description = "OK", body = Vec<LongResponser>),
+    ),
+)
+)]
+#[post("/moon")]
+pub async fn moon_long(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<DateRangeRequest>,
+) -> Result<impl Responder, Error> {
+    let ephemeris = Ephemeris::new(&app_state, &r)?;
+    let longs = body_longs(&ephemeris, &r, &Body::SeMoon)?;
+
+    Ok(json_response(&ephemeris, longs))
+}
+
+/// 新月的黄道经度
+/// 月亮黄道经度-太阳黄道经度，仅可用于coordinate=ecliptic、frame=geocentric
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="新月黄道经度",
+    context_path="/api",
+    request_body=DateRangeRequest,
+    responses(
+        (status = 200, description = "OK", body = Vec<LongResponser>),
+    ),
+)
+)]
+#[post("/new_moon")]
+pub async fn new_moon_long(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<DateRangeRequest>,
+) -> Result<impl Responder, Error> {
+    // 赤经之差不是月亮与太阳的距角
+    if r.coordinate != Coordinate::Ecliptic {
+        return Err(Error::BadRequest(
+            "新月黄道经度仅可用于coordinate=ecliptic".to_string(),
+        ));
+    }
+    // 日心、质心坐标中，太阳黄经为0或无意义
+    if r.frame != Frame::Geocentric {
+        return Err(Error::BadRequest(
+            "新月黄道经度仅可用于frame=geocentric".to_string(),
+        ));
+    }
+    let (start, end, d) = date_range(&r)?;
+    let ephemeris = Ephemeris::new(&app_state, &r)?;
+
+    let mut longs = vec![];
+    let mut date = start;
+    while date.jd_utc < end.jd_utc {
+        let sun_xx = ephemeris.calc(date.jd_utc, &Body::SeSun)?;
+        let moon_xx = ephemeris.calc(date.jd_utc, &Body::SeMoon)?;
+
+        let long = swe_degnorm(moon_xx[0] - sun_xx[0]);
+
+        let res = LongResponser::new(date.clone(), long)
+            .with_solar_time(observer_solar_time(&ephemeris, &r, &date)?);
+        // 月亮与太阳的距角，仅有经度速度
+        let res = if r.output == Output::Full {
+            res.with_long_speed(moon_xx[3] - sun_xx[3])
+        } else {
+            res
+        };
+        longs.push(res);
+
+        date = date.plus_days(d)?;
+    }
+
+    Ok(json_response(&ephemeris, longs))
+}
+
+/// 平均月交点（北交点）的黄道经度
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="平均月交点黄道经度",
+    context_path="/api",
+    request_body=DateRangeRequest,
+    responses(
+        (status = 200, description = "OK", body = Vec<LongResponser>),
+    ),
+)
+)]
+#[post("/mean_node")]
+pub async fn mean_node_long(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<DateRangeRequest>,
+) -> Result<impl Responder, Error> {
+    let ephemeris = Ephemeris::new(&app_state, &r)?;
+    let longs = body_longs(&ephemeris, &r, &Body::SeMeanNode)?;
+
+    Ok(json_response(&ephemeris, longs))
+}
+
+/// 真月交点（北交点）的黄道经度
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="真月交点黄道经度",
+    context_path="/api",
+    request_body=DateRangeRequest,
+    responses(
+        (status = 200, description = "OK", body = Vec<LongResponser>),
+    ),
+)
+)]
+#[post("/true_node")]
+pub async fn true_node_long(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<DateRangeRequest>,
+) -> Result<impl Responder, Error> {
+    let ephemeris = Ephemeris::new(&app_state, &r)?;
+    let longs = body_longs(&ephemeris, &r, &Body::SeTrueNode)?;
+
+    Ok(json_response(&ephemeris, longs))
+}
+
+/// 平均月远地点（黑月莉莉丝）的黄道经度
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="平均月远地点黄道经度",
+    context_path="/api",
+    request_body=DateRangeRequest,
+    responses(
+        (status = 200, description = "OK", body = Vec<LongResponser>),
+    ),
+)
+)]
+#[post("/mean_apogee")]
+pub async fn mean_apogee_long(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<DateRangeRequest>,
+) -> Result<impl Responder, Error> {
+    let ephemeris = Ephemeris::new(&app_state, &r)?;
+    let longs = body_longs(&ephemeris, &r, &Body::SeMeanApog)?;
+
+    Ok(json_response(&ephemeris, longs))
+}
+
+/// 密切月远地点的黄道经度
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="密切月远地点黄道经度",
+    context_path="/api",
+    request_body=DateRangeRequest,
+    responses(
+        (status = 200, description = "OK", body = Vec<LongResponser>),
+    ),
+)
+)]
+#[post("/oscu_apogee")]
+pub async fn oscu_apogee_long(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<DateRangeRequest>,
+) -> Result<impl Responder, Error> {
+    let ephemeris = Ephemeris::new(&app_state, &r)?;
+    let longs = body_longs(&ephemeris, &r, &Body::SeOscuApog)?;
+
+    Ok(json_response(&ephemeris, longs))
+}
+
+/// 任一星体的黄道经度
+/// name: sun, moon, mercury, venus, mars, jupiter, saturn, uranus, neptune, pluto,
+/// mean_node, true_node, mean_apog, oscu_apog, earth, chiron, pholus, ceres, pallas, juno, vesta
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="星体黄道经度",
+    context_path="/api",
+    params(
+        ("name" = String, Path, description = "星体名称，如：mercury、true_node、chiron"),
+    ),
+    request_body=DateRangeRequest,
+    responses(
+        (status = 200, description = "OK", body = Vec<LongResponser>),
+        (status = 400, description = "不支持的星体"),
+    ),
+)
+)]
+#[post("/body/{name}")]
+pub async fn body_long(
+    app_state: web::Data<AppState>,
+    name: web::Path<String>,
+    r: actix_web_validator::Json<DateRangeRequest>,
+) -> Result<impl Responder, Error> {
+    let body = body_from_name(&name)?;
+    let ephemeris = Ephemeris::new(&app_state, &r)?;
+    let longs = body_longs(&ephemeris, &r, &body)?;
+
+    Ok(json_response(&ephemeris, longs))
+}
+
+/// 恒星的黄道经度、黄道纬度
+/// name为恒星名称，如：Spica、Regulus、Antares，或星表sefstars.txt中的序号
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="恒星黄道经度",
+    context_path="/api",
+    params(
+        ("name" = String, Path, description = "恒星名称，如：Spica"),
+    ),
+    request_body=DateRangeRequest,
+    responses(
+        (status = 200, description = "OK", body = Vec<LongResponser>),
+        (status = 400, description = "星表中没有该恒星"),
+    ),
+)
+)]
+#[post("/star/{name}")]
+pub async fn star_long(
+    app_state: web::Data<AppState>,
+    name: web::Path<String>,
+    r: actix_web_validator::Json<DateRangeRequest>,
+) -> Result<impl Responder, Error> {
+    let (start, end, d) = date_range(&r)?;
+    let ephemeris = Ephemeris::new(&app_state, &r)?;
+
+    let mut dates = vec![];
+    let mut date = start;
+    while date.jd_utc < end.jd_utc {
+        dates.push(date.clone());
+        date = date.plus_days(d)?;
+    }
+    // 整个序列只读取一次星表
+    let jds: Vec<f64> = dates.iter().map(|date| date.jd_utc).collect();
+    let series = ephemeris.calc_star_series(&jds, &name)?;
+
+    let mut longs = vec![];
+    for (date, xx) in dates.into_iter().zip(series) {
+        let res = match r.output {
+            Output::Compact => LongResponser::new(date.clone(), xx[0]).with_lat(xx[1]),
+            Output::Full => LongResponser::from_xx(date.clone(), &xx),
+        };
+        let res = res.with_solar_time(observer_solar_time(&ephemeris, &r, &date)?);
+        longs.push(res);
+    }
+
+    Ok(json_response(&ephemeris, longs))
+}
+
+/// 黄道附近的恒星
+/// 列出星表sefstars.txt中，给定时刻黄道纬度绝对值不大于max_lat的恒星
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="恒星黄道经度",
+    context_path="/api",
+    request_body=StarsRequest,
+    responses(
+        (status = 200, description = "OK", body = Vec<StarResponser>),
+    ),
+)
+)]
+#[post("/stars")]
+pub async fn ecliptic_stars(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<StarsRequest>,
+) -> Result<impl Responder, Error> {
+    let date = horo_date(&r.date, 8.0)?;
+    let ephemeris = Ephemeris::with_flags(&app_state, vec![]);
+
+    let catalogue = ephemeris.calc_stars(date.jd_utc)?;
+    if catalogue.is_empty() {
+        return Err(Error::Function("读取恒星星表sefstars.txt错误".to_string()));
+    }
+    let stars: Vec<_> = catalogue
+        .into_iter()
+        .filter(|(_, xx)| xx[1].abs() <= r.max_lat)
+        .map(|(name, xx)| StarResponser::new(name, xx[0], xx[1]))
+        .collect();
+
+    Ok(json_response(&ephemeris, stars))
+}
+
+/// 岁差
+/// 以请求中的ayanamsa计算岁差值，忽略zodiac、coordinate、frame、observer等其余选项
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="岁差",
+    context_path="/api",
+    request_body=DateRangeRequest,
+    responses(
+        (status = 200, description = "OK", body = Vec<LongResponser>),
+    ),
+)
+)]
+#[post("/ayanamsa")]
+pub async fn ayanamsa(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<DateRangeRequest>,
+) -> Result<impl Responder, Error> {
+    let (start, end, d) = date_range(&r)?;
+    let ephemeris = Ephemeris::with_flags(&app_state, vec![]).with_ayanamsa(r.ayanamsa);
+
+    let mut longs = vec![];
+    let mut date = start;
+    while date.jd_utc < end.jd_utc {
+        let long = ephemeris.ayanamsa(date.jd_utc)?;
+        let res = LongResponser::new(date.clone(), long);
+        longs.push(res);
+
+        date = date.plus_days(d)?;
+    }
+
+    Ok(json_response(&ephemeris, longs))
+}
+
+/// 计算给定区间内星体的黄道经度
+/// coordinate为赤道坐标时，计算赤经
+/// zodiac为sidereal时，计算恒星黄道经度
+/// position给出光行差、光行时、章动等选项
+/// frame为日心或质心时，计算日心或质心坐标
+/// 给出observer时，计算站心坐标，并输出地方平太阳时、真太阳时
+/// 区间小于1天，步长为1小时，否则步长为1天
+fn body_longs(
+    ephemeris: &Ephemeris,
+    r: &DateRangeRequest,
+    body: &Body,
+) -> Result<Vec<LongResponser>, Error> {
+    let (start, end, d) = date_range(r)?;
+
+    let mut longs = vec![];
+    let mut date = start;
+    while date.jd_utc < end.jd_utc {
+        let xx = ephemeris.calc(date.jd_utc, body)?;
+
+        let res = match r.output {
+            // 赤道坐标只给赤经无法定位，一并给出赤纬
+            Output::Compact if r.coordinate != Coordinate::Ecliptic => {
+                LongResponser::new(date.clone(), xx[0]).with_lat(xx[1])
+            }
+            Output::Compact => LongResponser::new(date.clone(), xx[0]),
+            Output::Full => LongResponser::from_xx(date.clone(), &xx),
+        };
+        let res = res.with_solar_time(observer_solar_time(ephemeris, r, &date)?);
+        longs.push(res);
+
+        date = date.plus_days(d)?;
+    }
+
+    Ok(longs)
+}
+
+/// 给出observer时，计算观测者所在地的地方平太阳时、真太阳时
+fn observer_solar_time(
+    ephemeris: &Ephemeris,
+    r: &DateRangeRequest,
+    date: &HoroDateTime,
+) -> Result<Option<SolarTimeResponser>, Error> {
+    r.observer
+        .map(|observer| solar_time(ephemeris, date.jd_utc, observer.long).map(Into::into))
+        .transpose()
+}
@@ -0,0 +1,123 @@
+use actix_web::{post, web, Responder};
+use swe::Flag;
+
+use crate::{
+    apsis::{lunar_apsides, syzygies},
+    ephemeris::Ephemeris,
+    error::Error,
+    lunar_phase::{lunar_phases, lunation_number, Phase},
+    request::{ApsisRequest, EventRequest},
+    response::{ApsidesResponser, ApsisResponser, LunationResponser, SyzygyResponser},
+    state::AppState,
+};
+
+use super::{event_range, json_response};
+
+/// 朔望
+/// 以/new_moon的月亮与太阳黄经差求根，得到区间内每次朔、望的精确时刻
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="朔望",
+    context_path="/api",
+    request_body=EventRequest,
+    responses(
+        (status = 200, description = "OK", body = Vec<LunationResponser>),
+    ),
+)
+)]
+#[post("/lunations")]
+pub async fn lunations(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<EventRequest>,
+) -> Result<impl Responder, Error> {
+    let (start, end) = event_range(&r)?;
+    let ephemeris = Ephemeris::with_flags(&app_state, vec![]);
+
+    let phases = lunar_phases(
+        &ephemeris,
+        start.jd_utc,
+        end.jd_utc,
+        &[Phase::NewMoon, Phase::FullMoon],
+    )?;
+    let lunations = phases
+        .into_iter()
+        .map(|(phase, jd)| LunationResponser::new(phase, lunation_number(phase, jd), jd, r.tz))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(json_response(&ephemeris, lunations))
+}
+
+/// 月相
+/// 区间内朔、上弦、望、下弦的精确时刻，即月亮与太阳黄经差为0、90、180、270度的时刻
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="朔望",
+    context_path="/api",
+    request_body=EventRequest,
+    responses(
+        (status = 200, description = "OK", body = Vec<LunationResponser>),
+    ),
+)
+)]
+#[post("/lunar_phases")]
+pub async fn lunar_phase_events(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<EventRequest>,
+) -> Result<impl Responder, Error> {
+    let (start, end) = event_range(&r)?;
+    let ephemeris = Ephemeris::with_flags(&app_state, vec![]);
+
+    let phases = lunar_phases(
+        &ephemeris,
+        start.jd_utc,
+        end.jd_utc,
+        &[
+            Phase::NewMoon,
+            Phase::FirstQuarter,
+            Phase::FullMoon,
+            Phase::LastQuarter,
+        ],
+    )?;
+    let phases = phases
+        .into_iter()
+        .map(|(phase, jd)| LunationResponser::new(phase, lunation_number(phase, jd), jd, r.tz))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(json_response(&ephemeris, phases))
+}
+
+/// 月亮近地点、远地点
+/// 求区间内月亮过近地点、远地点的精确时刻及月地距离
+/// 并给出区间内的朔、望，月地距离不超过supermoon为超级月亮，不小于micromoon为微型月亮
+#[cfg_attr(feature = "swagger",
+utoipa::path(
+    tag="月亮近地点、远地点",
+    context_path="/api",
+    request_body=ApsisRequest,
+    responses(
+        (status = 200, description = "OK", body = ApsidesResponser),
+    ),
+)
+)]
+#[post("/lunar_apsides")]
+pub async fn lunar_apsis_events(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<ApsisRequest>,
+) -> Result<impl Responder, Error> {
+    let (start, end) = event_range(&r.range)?;
+    let ephemeris = Ephemeris::with_flags(&app_state, vec![Flag::SeflgSpeed]);
+
+    let apsides = lunar_apsides(&ephemeris, start.jd_utc, end.jd_utc)?
+        .iter()
+        .map(|apsis| ApsisResponser::new(apsis, r.range.tz))
+        .collect::<Result<Vec<_>, Error>>()?;
+    let syzygies = syzygies(&ephemeris, start.jd_utc, end.jd_utc)?
+        .iter()
+        .map(|syzygy| SyzygyResponser::new(syzygy, r.supermoon, r.micromoon, r.range.tz))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(json_response(
+        &ephemeris,
+        ApsidesResponser::new(apsides, syzygies),
+    ))
+}
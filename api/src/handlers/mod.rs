@@ -0,0 +1,88 @@
+use actix_web::HttpResponse;
+use serde::Serialize;
+
+use crate::{
+    ephemeris::Ephemeris,
+    error::{DateTimeError, Error},
+    horo_date_time::{horo_date_time, HoroDateTime},
+    request::{DateRangeRequest, DateRequest, EventRequest},
+};
+
+mod astrology;
+mod calendar;
+mod eclipse;
+mod horizon;
+mod longitude;
+mod lunar;
+
+pub use self::{astrology::*, calendar::*, eclipse::*, horizon::*, longitude::*, lunar::*};
+
+/// 返回json，响应头给出实际使用的星历表
+fn json_response(ephemeris: &Ephemeris, body: impl Serialize) -> HttpResponse {
+    HttpResponse::Ok()
+        .insert_header(ephemeris.backend_header())
+        .json(body)
+}
+
+/// 由请求得到时间
+fn horo_date(d: &DateRequest, tz: f64) -> Result<HoroDateTime, Error> {
+    horo_date_time(
+        d.year, d.month, d.day, d.hour, d.minute, d.second, tz, false,
+    )
+}
+
+/// 由请求得到起止日期间，各日当地0时
+fn local_days(r: &EventRequest) -> Result<Vec<HoroDateTime>, Error> {
+    let local_day = |d: &DateRequest| horo_date_time(d.year, d.month, d.day, 0, 0, 0, r.tz, false);
+    let start = local_day(&r.start)?;
+    let end = local_day(&r.end)?;
+
+    if end.jd_utc < start.jd_utc {
+        let err = DateTimeError::InvalidDateTime("start date 必需不大于 end date".to_string());
+        return Err(err.into());
+    }
+
+    let mut days = vec![];
+    let mut day = start;
+    while day.jd_utc < end.jd_utc + 0.5 {
+        let next = day.plus_days(1.0)?;
+        days.push(day);
+        day = next;
+    }
+    Ok(days)
+}
+
+/// 由请求得到起止时间
+fn time_range(
+    start: &DateRequest,
+    end: &DateRequest,
+    tz: f64,
+) -> Result<(HoroDateTime, HoroDateTime), Error> {
+    let start = horo_date(start, tz)?;
+    let end = horo_date(end, tz)?;
+
+    if end.jd_utc <= start.jd_utc {
+        let err = DateTimeError::InvalidDateTime("start date 必需小于 end date".to_string());
+        return Err(err.into());
+    }
+
+    Ok((start, end))
+}
+
+/// 由区间请求得到起止时间
+fn event_range(r: &EventRequest) -> Result<(HoroDateTime, HoroDateTime), Error> {
+    time_range(&r.start, &r.end, r.tz)
+}
+
+/// 由请求得到起止时间及步长
+fn date_range(r: &DateRangeRequest) -> Result<(HoroDateTime, HoroDateTime, f64), Error> {
+    let (start, end) = time_range(&r.start, &r.end, 8.0)?;
+
+    let d = if end.jd_utc - start.jd_utc < 1.0 {
+        1.0 / 24.0
+    } else {
+        1.0
+    };
+
+    Ok((start, end, d))
+}
@@ -0,0 +1,286 @@
+use swe::{swe_degnorm, Body};
+
+use crate::{
+    aspect::MAJOR_ASPECTS,
+    body::body_name,
+    ephemeris::Ephemeris,
+    error::Error,
+    search::{angle_diff, bisect, boundary_crossings},
+};
+
+/// 黄道十二宫，序号0为白羊，起于黄经0度
+pub const SIGN_NAMES: [&str; 12] = [
+    "白羊", "金牛", "双子", "巨蟹", "狮子", "处女", "天秤", "天蝎", "射手", "摩羯", "水瓶", "双鱼",
+];
+
+/// 每宫30度
+const SIGN_WIDTH: f64 = 30.0;
+
+/// 月亮空亡所考虑的行星
+const VOID_OF_COURSE_BODIES: [Body; 9] = [
+    Body::SeSun,
+    Body::SeMercury,
+    Body::SeVenus,
+    Body::SeMars,
+    Body::SeJupiter,
+    Body::SeSaturn,
+    Body::SeUranus,
+    Body::SeNeptune,
+    Body::SePluto,
+];
+
+/// 入宫
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Ingress {
+    /// 进入的宫的序号，0为白羊
+    pub sign: usize,
+    /// UTC时的儒略日
+    pub jd_utc: f64,
+    /// 是否逆行入宫
+    pub retrograde: bool,
+}
+
+impl Ingress {
+    /// 宫名，如：白羊
+    pub fn sign_name(&self) -> &'static str {
+        SIGN_NAMES[self.sign]
+    }
+
+    /// 越过的宫界的黄经，顺行为所进入宫的起点，逆行为所进入宫的终点
+    pub fn long(&self) -> f64 {
+        if self.retrograde {
+            ((self.sign + 1) % SIGN_NAMES.len()) as f64 * SIGN_WIDTH
+        } else {
+            self.sign as f64 * SIGN_WIDTH
+        }
+    }
+}
+
+/// 月亮空亡
+/// 月亮与行星的最后一个主要相位，至月亮入下一宫
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct VoidOfCourse {
+    /// 开始，UTC时的儒略日
+    pub start: f64,
+    /// 结束，即入宫，UTC时的儒略日
+    pub end: f64,
+    /// 结束时进入的宫的序号
+    pub sign: usize,
+    /// 最后一个主要相位的行星中文名及相位，上一宫内没有主要相位时为None，空亡自入上一宫起
+    pub aspect: Option<(&'static str, f64)>,
+}
+
+/// 星体的黄道经度
+fn long(ephemeris: &Ephemeris, jd_utc: f64, body: &Body) -> Result<f64, Error> {
+    Ok(ephemeris.calc(jd_utc, body)?[0])
+}
+
+/// 求[start, end]内星体进入各宫的时刻
+pub fn ingresses(
+    ephemeris: &Ephemeris,
+    body: &Body,
+    start: f64,
+    end: f64,
+) -> Result<Vec<Ingress>, Error> {
+    // 月亮约2.5日过一宫，其余星体以1日为步长
+    let step = if matches!(body, Body::SeMoon) {
+        0.25
+    } else {
+        1.0
+    };
+    let f = |jd| long(ephemeris, jd, body);
+    boundary_crossings(f, SIGN_WIDTH, start, end, step)?
+        .into_iter()
+        .map(|(sign, jd_utc)| -> Result<Ingress, Error> {
+            // 以前后1小时的黄经判断顺逆
+            let d = 1.0 / 24.0;
+            let retrograde = angle_diff(f(jd_utc + d)?, f(jd_utc - d)?) < 0.0;
+            Ok(Ingress {
+                sign,
+                jd_utc,
+                retrograde,
+            })
+        })
+        .collect()
+}
+
+/// 月亮与行星间角距为index*30度时，所成的主要相位，不是主要相位时为None
+fn major_aspect(index: usize) -> Option<f64> {
+    let angle = (index % 12) as f64 * 30.0;
+    let angle = angle.min(360.0 - angle);
+    MAJOR_ASPECTS.contains(&angle).then_some(angle)
+}
+
+/// 求[start, end]内月亮与行星成主要相位的时刻
+/// 返回行星中文名、相位及时刻，按时间排序
+fn moon_aspects(
+    ephemeris: &Ephemeris,
+    start: f64,
+    end: f64,
+) -> Result<Vec<(&'static str, f64, f64)>, Error> {
+    // 每个采样时刻，月亮只计算一次
+    let bodies: Vec<&Body> = std::iter::once(&Body::SeMoon)
+        .chain(&VOID_OF_COURSE_BODIES)
+        .collect();
+    let separations = |jd| -> Result<Vec<f64>, Error> {
+        let xx = ephemeris.calc_bodies(jd, &bodies)?;
+        Ok(xx[1..]
+            .iter()
+            .map(|x| swe_degnorm(xx[0][0] - x[0]))
+            .collect())
+    };
+    // 角距每30度为一个区间，同major_aspect()
+    let index = |separation: f64| (separation / 30.0).floor() as usize % 12;
+
+    let mut aspects = vec![];
+    let mut jd0 = start;
+    let mut separations0 = separations(jd0)?;
+    while jd0 < end {
+        let jd1 = (jd0 + 0.25).min(end);
+        let separations1 = separations(jd1)?;
+        for (i, body) in VOID_OF_COURSE_BODIES.iter().enumerate() {
+            let next = index(separations1[i]);
+            if index(separations0[i]) == next {
+                continue;
+            }
+            let Some(angle) = major_aspect(next) else {
+                continue;
+            };
+            // 月亮快于各行星，月亮与行星的角距总是增加，越过的是所进入区间的起点
+            let boundary = next as f64 * 30.0;
+            let f = |jd| {
+                let xx = ephemeris.calc_bodies(jd, &[&Body::SeMoon, body])?;
+                Ok(angle_diff(xx[0][0] - xx[1][0], boundary))
+            };
+            aspects.push((body_name(body), angle, bisect(f, jd0, jd1)?));
+        }
+        jd0 = jd1;
+        separations0 = separations1;
+    }
+    aspects.sort_by(|a, b| a.2.total_cmp(&b.2));
+    Ok(aspects)
+}
+
+/// 求与[start, end]相交的月亮空亡
+pub fn void_of_course(
+    ephemeris: &Ephemeris,
+    start: f64,
+    end: f64,
+) -> Result<Vec<VoidOfCourse>, Error> {
+    // 月亮过一宫不超过3日，前后各多算3日，以得到区间两端的空亡
+    let ingresses = ingresses(ephemeris, &Body::SeMoon, start - 3.0, end + 3.0)?;
+    let Some(first) = ingresses.first() else {
+        return Ok(vec![]);
+    };
+    let aspects = moon_aspects(ephemeris, first.jd_utc, end + 3.0)?;
+
+    let voids = ingresses
+        .windows(2)
+        .map(|w| {
+            let (prev, next) = (w[0], w[1]);
+            let last = aspects
+                .iter()
+                .rev()
+                .find(|(_, _, jd)| prev.jd_utc < *jd && *jd < next.jd_utc);
+            VoidOfCourse {
+                start: last.map_or(prev.jd_utc, |(_, _, jd)| *jd),
+                end: next.jd_utc,
+                sign: next.sign,
+                aspect: last.map(|&(body, angle, _)| (body, angle)),
+            }
+        })
+        .filter(|void| void.start <= end && void.end >= start)
+        .collect();
+    Ok(voids)
+}
+
+#[cfg(test)]
+mod test {
+    use swe::Body;
+
+    use super::{ingresses, major_aspect, void_of_course, Ingress};
+    use crate::{search::angle_diff, test_util::moshier};
+
+    #[test]
+    fn test_ingress_long() {
+        let ingress = Ingress {
+            sign: 0,
+            jd_utc: 0.0,
+            retrograde: false,
+        };
+        assert_eq!("白羊", ingress.sign_name());
+        assert_eq!(0.0, ingress.long());
+
+        // 逆行进入双鱼，越过白羊起点
+        let ingress = Ingress {
+            sign: 11,
+            jd_utc: 0.0,
+            retrograde: true,
+        };
+        assert_eq!("双鱼", ingress.sign_name());
+        assert_eq!(0.0, ingress.long());
+
+        let ingress = Ingress {
+            sign: 4,
+            jd_utc: 0.0,
+            retrograde: true,
+        };
+        assert_eq!(150.0, ingress.long());
+    }
+
+    #[test]
+    fn test_major_aspect() {
+        assert_eq!(Some(0.0), major_aspect(0));
+        assert_eq!(None, major_aspect(1));
+        assert_eq!(Some(60.0), major_aspect(2));
+        assert_eq!(Some(90.0), major_aspect(3));
+        assert_eq!(Some(120.0), major_aspect(4));
+        assert_eq!(None, major_aspect(5));
+        assert_eq!(Some(180.0), major_aspect(6));
+        assert_eq!(None, major_aspect(7));
+        assert_eq!(Some(120.0), major_aspect(8));
+        assert_eq!(Some(90.0), major_aspect(9));
+        assert_eq!(Some(60.0), major_aspect(10));
+        assert_eq!(None, major_aspect(11));
+    }
+
+    #[test]
+    fn test_moon_ingress() {
+        let ephemeris = moshier(vec![]);
+
+        // 2024-04-09 11:23 UTC，月亮入金牛
+        let ingresses = ingresses(&ephemeris, &Body::SeMoon, 2460409.5, 2460410.5).unwrap();
+        assert_eq!(1, ingresses.len());
+        let ingress = ingresses[0];
+        assert_eq!("金牛", ingress.sign_name());
+        assert!(!ingress.retrograde);
+        assert!(
+            (ingress.jd_utc - 2460409.97455).abs() < 1.0 / 1440.0,
+            "{}",
+            ingress.jd_utc
+        );
+    }
+
+    #[test]
+    fn test_void_of_course() {
+        let ephemeris = moshier(vec![]);
+
+        // 2024-04-08日食后，月亮在白羊合逆行的水星，此后至入金牛，与各行星不再成主要相位
+        let voids = void_of_course(&ephemeris, 2460409.5, 2460409.6).unwrap();
+        let void = voids.iter().find(|void| void.sign == 1).unwrap();
+        assert!(
+            (void.end - 2460409.97455).abs() < 1.0 / 1440.0,
+            "{}",
+            void.end
+        );
+        assert_eq!(Some(("水星", 0.0)), void.aspect);
+
+        // 空亡开始时，月亮与水星黄经相同，且在入宫之前
+        assert!(void.start < void.end);
+        let xx = ephemeris
+            .calc_bodies(void.start, &[&Body::SeMoon, &Body::SeMercury])
+            .unwrap();
+        assert!(angle_diff(xx[0][0], xx[1][0]).abs() < 1e-4);
+        assert!((20.0..30.0).contains(&xx[0][0]), "{}", xx[0][0]);
+    }
+}
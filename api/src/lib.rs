@@ -1,11 +1,28 @@
+pub mod apsis;
 pub mod args;
+pub mod aspect;
+pub mod body;
+pub mod eclipse;
+pub mod ephemeris;
 pub mod error;
+pub mod ganzhi;
 pub mod handlers;
 pub mod horo_date_time;
+pub mod ingress;
+pub mod lunar_calendar;
+pub mod lunar_phase;
 pub mod request;
 pub mod response;
+pub mod rise_set;
 pub mod routers;
+pub mod search;
+pub mod solar_term;
+pub mod solar_time;
 pub mod state;
+pub mod station;
+
+#[cfg(test)]
+mod test_util;
 
 #[cfg(feature = "swagger")]
 pub mod swagger;
@@ -0,0 +1,364 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+};
+
+use crate::{
+    ephemeris::Ephemeris,
+    error::{DateTimeError, Error},
+    horo_date_time::HoroDateTime,
+    lunar_phase::{lunar_phases, Phase},
+    solar_term::solar_terms,
+    state::Backend,
+};
+
+/// 农历以东8区的日期为准
+const CHINA_TZ: f64 = 8.0;
+
+/// 冬至的节气序号
+const DONG_ZHI: usize = 18;
+
+const MONTH_NAMES: [&str; 12] = [
+    "正月", "二月", "三月", "四月", "五月", "六月", "七月", "八月", "九月", "十月", "冬月", "腊月",
+];
+
+/// 已计算的岁的农历月，以岁及所用星历表为键
+type SuiCache = HashMap<(i32, Backend), Vec<LunarMonth>>;
+
+/// 每岁须查找约400日的节气及朔，故在进程内缓存
+static SUI_CACHE: OnceLock<Mutex<SuiCache>> = OnceLock::new();
+
+const DAY_NAMES: [&str; 30] = [
+    "初一", "初二", "初三", "初四", "初五", "初六", "初七", "初八", "初九", "初十", "十一", "十二",
+    "十三", "十四", "十五", "十六", "十七", "十八", "十九", "二十", "廿一", "廿二", "廿三", "廿四",
+    "廿五", "廿六", "廿七", "廿八", "廿九", "三十",
+];
+
+/// 农历月
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct LunarMonth {
+    /// 农历年，以正月所在的公历年表示
+    pub year: i32,
+    /// 月，1-12
+    pub month: u8,
+    /// 是否为闰月
+    pub leap: bool,
+    /// 朔日，东8区的儒略日数，即东8区该日12:00的儒略日
+    pub start: i64,
+    /// 本月天数，29或30
+    pub days: u8,
+}
+
+/// 农历日期
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct LunarDate {
+    pub month: LunarMonth,
+    /// 日，1-30
+    pub day: u8,
+}
+
+impl LunarDate {
+    /// 月名，如：闰四月、正月
+    pub fn month_name(&self) -> String {
+        month_name(self.month.month, self.month.leap)
+    }
+
+    /// 日名，如：初一、廿三
+    pub fn day_name(&self) -> &'static str {
+        DAY_NAMES[usize::from(self.day) - 1]
+    }
+}
+
+/// 东8区的儒略日数
+fn china_day(jd_utc: f64) -> i64 {
+    (jd_utc + CHINA_TZ / 24.0 + 0.5).floor() as i64
+}
+
+/// 月名，如：闰四月、正月
+pub fn month_name(month: u8, leap: bool) -> String {
+    let name = MONTH_NAMES[usize::from(month) - 1];
+    if leap {
+        format!("闰{name}")
+    } else {
+        name.to_string()
+    }
+}
+
+/// 东8区公历日期0时的UTC儒略日
+fn jd_of_date(year: i32, month: u8, day: u8) -> Result<f64, Error> {
+    let t = HoroDateTime::new(year, month, day, 0, 0, 0, CHINA_TZ)?;
+    Ok(t.jd_utc)
+}
+
+/// 一岁的农历月，即公历year-1年冬至所在月（冬月）至公历year年冬至所在月之前
+/// 已计算过的岁取自缓存
+pub fn sui_months(ephemeris: &Ephemeris, year: i32) -> Result<Vec<LunarMonth>, Error> {
+    let cache = SUI_CACHE.get_or_init(Default::default);
+    if let Some(months) = cache
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(&(year, ephemeris.backend())).cloned())
+    {
+        return Ok(months);
+    }
+
+    let months = compute_sui_months(ephemeris, year)?;
+    // 改用Moshier星历表计算时，以Moshier为键
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert((year, ephemeris.backend()), months.clone());
+    }
+    Ok(months)
+}
+
+/// 计算一岁的农历月
+/// 冬至所在月为冬月，一岁有13个月时，第一个不含中气的月为闰月
+fn compute_sui_months(ephemeris: &Ephemeris, year: i32) -> Result<Vec<LunarMonth>, Error> {
+    let start = jd_of_date(year - 1, 11, 15)?;
+    let end = jd_of_date(year, 12, 31)?;
+
+    let terms = solar_terms(ephemeris, start, end)?;
+    let dong_zhi: Vec<i64> = terms
+        .iter()
+        .filter(|term| term.index == DONG_ZHI)
+        .map(|term| china_day(term.jd_utc))
+        .collect();
+    if dong_zhi.len() != 2 {
+        return Err(Error::Function(format!("计算{year}年冬至错误")));
+    }
+    let zhong_qi: Vec<i64> = terms
+        .iter()
+        .filter(|term| term.is_zhong_qi())
+        .map(|term| china_day(term.jd_utc))
+        .collect();
+
+    let new_moons: Vec<i64> = lunar_phases(ephemeris, start - 30.0, end, &[Phase::NewMoon])?
+        .into_iter()
+        .map(|(_, jd)| china_day(jd))
+        .collect();
+
+    // 冬至所在月的朔日，即冬至当日或之前最近的朔日
+    let month_11 = |dong_zhi: i64| {
+        new_moons
+            .iter()
+            .rposition(|&new_moon| new_moon <= dong_zhi)
+            .ok_or_else(|| Error::Function(format!("计算{year}年冬月错误")))
+    };
+    let first = month_11(dong_zhi[0])?;
+    let last = month_11(dong_zhi[1])?;
+
+    let has_zhong_qi = |i: usize| {
+        zhong_qi
+            .iter()
+            .any(|&day| new_moons[i] <= day && day < new_moons[i + 1])
+    };
+    let mut leap_month = if last - first == 13 {
+        (first..last).find(|&i| !has_zhong_qi(i))
+    } else {
+        None
+    };
+
+    let mut months = vec![];
+    let mut month = 10;
+    let mut lunar_year = year - 1;
+    for i in first..last {
+        let leap = leap_month == Some(i);
+        if leap {
+            // 一岁只有一个闰月
+            leap_month = None;
+        } else {
+            month = month % 12 + 1;
+            if month == 1 {
+                lunar_year = year;
+            }
+        }
+        months.push(LunarMonth {
+            year: lunar_year,
+            month,
+            leap,
+            start: new_moons[i],
+            days: (new_moons[i + 1] - new_moons[i]) as u8,
+        });
+    }
+    Ok(months)
+}
+
+/// 公历转农历
+pub fn to_lunar(ephemeris: &Ephemeris, jd_utc: f64) -> Result<LunarDate, Error> {
+    let day = china_day(jd_utc);
+    let year = HoroDateTime::from_jd_zone(jd_utc, CHINA_TZ)?.year;
+
+    // 公历year年冬月及之后的日期，属于下一岁
+    for year in [year, year + 1] {
+        let months = sui_months(ephemeris, year)?;
+        let month = months
+            .into_iter()
+            .find(|month| month.start <= day && day < month.start + i64::from(month.days));
+        if let Some(month) = month {
+            return Ok(LunarDate {
+                month,
+                day: (day - month.start + 1) as u8,
+            });
+        }
+    }
+    Err(Error::Function("公历转农历错误".to_string()))
+}
+
+/// 农历转公历
+/// 返回东8区农历该日0时的儒略日
+pub fn from_lunar(
+    ephemeris: &Ephemeris,
+    year: i32,
+    month: u8,
+    day: u8,
+    leap: bool,
+) -> Result<(LunarDate, f64), Error> {
+    // 正月至十月在公历year年的岁中，冬月、腊月可能在下一岁中
+    for sui in [year, year + 1] {
+        let months = sui_months(ephemeris, sui)?;
+        let lunar_month = months
+            .into_iter()
+            .find(|m| m.year == year && m.month == month && m.leap == leap);
+        if let Some(lunar_month) = lunar_month {
+            if day > lunar_month.days {
+                let msg = format!(
+                    "农历{year}年{}只有{}天",
+                    month_name(month, leap),
+                    lunar_month.days
+                );
+                return Err(DateTimeError::InvalidDateTime(msg).into());
+            }
+            let china_day = lunar_month.start + i64::from(day) - 1;
+            let jd_utc = china_day as f64 - 0.5 - CHINA_TZ / 24.0;
+            let date = LunarDate {
+                month: lunar_month,
+                day,
+            };
+            return Ok((date, jd_utc));
+        }
+    }
+    let msg = format!("农历{year}年没有{}", month_name(month, leap));
+    Err(DateTimeError::InvalidDateTime(msg).into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        china_day, from_lunar, jd_of_date, month_name, sui_months, to_lunar, LunarDate, LunarMonth,
+    };
+    use crate::test_util::moshier;
+
+    #[test]
+    fn test_china_day() {
+        // 2000-01-01 00:01 东8区 = 1999-12-31 16:01 UTC
+        assert_eq!(2451545, china_day(2451544.1674));
+        // 2000-01-01 23:59 东8区
+        assert_eq!(2451545, china_day(2451545.1660));
+        // 2000-01-02 00:01 东8区
+        assert_eq!(2451546, china_day(2451545.1674));
+    }
+
+    #[test]
+    fn test_month_name() {
+        assert_eq!("正月", month_name(1, false));
+        assert_eq!("闰四月", month_name(4, true));
+        assert_eq!("冬月", month_name(11, false));
+        assert_eq!("腊月", month_name(12, false));
+    }
+
+    #[test]
+    fn test_day_name() {
+        let month = LunarMonth {
+            year: 2023,
+            month: 2,
+            leap: true,
+            start: 2460034,
+            days: 29,
+        };
+        let names = [
+            (1, "初一"),
+            (10, "初十"),
+            (20, "二十"),
+            (23, "廿三"),
+            (30, "三十"),
+        ];
+        for (day, name) in names {
+            let date = LunarDate { month, day };
+            assert_eq!(name, date.day_name());
+        }
+        assert_eq!("闰二月", LunarDate { month, day: 1 }.month_name());
+    }
+
+    #[test]
+    fn test_leap_month() {
+        let ephemeris = moshier(vec![]);
+
+        // 2023年闰二月，朔日为2023-03-22
+        let months = sui_months(&ephemeris, 2023).unwrap();
+        assert_eq!(13, months.len());
+        let leap: Vec<_> = months.iter().filter(|month| month.leap).collect();
+        assert_eq!(1, leap.len());
+        assert_eq!((2023, 2), (leap[0].year, leap[0].month));
+        assert_eq!(china_day(jd_of_date(2023, 3, 22).unwrap()), leap[0].start);
+
+        // 2033年闰十一月，朔日为2033-12-22，在2034岁中
+        let months = sui_months(&ephemeris, 2034).unwrap();
+        let leap: Vec<_> = months.iter().filter(|month| month.leap).collect();
+        assert_eq!(1, leap.len());
+        assert_eq!((2033, 11), (leap[0].year, leap[0].month));
+        let (_, jd_utc) = from_lunar(&ephemeris, 2033, 11, 1, true).unwrap();
+        assert_eq!(
+            china_day(jd_of_date(2033, 12, 22).unwrap()),
+            china_day(jd_utc)
+        );
+
+        // 2024年没有闰月
+        let months = sui_months(&ephemeris, 2024).unwrap();
+        assert_eq!(12, months.len());
+        assert!(months.iter().all(|month| !month.leap));
+    }
+
+    #[test]
+    fn test_spring_festival() {
+        let ephemeris = moshier(vec![]);
+
+        // 春节
+        let dates = [(2023, 1, 22), (2024, 2, 10), (2025, 1, 29), (2033, 1, 31)];
+        for (year, month, day) in dates {
+            let jd_utc = jd_of_date(year, month, day).unwrap();
+            let date = to_lunar(&ephemeris, jd_utc + 0.5).unwrap();
+            assert_eq!((year, 1, false, 1), date_key(&date));
+
+            // 前一日为上一年腊月
+            let date = to_lunar(&ephemeris, jd_utc - 0.5).unwrap();
+            assert_eq!(
+                (year - 1, 12, false),
+                (date.month.year, date.month.month, date.month.leap)
+            );
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let ephemeris = moshier(vec![]);
+
+        // 2023-01-01起，每5日一次，共约2年
+        let start = jd_of_date(2023, 1, 1).unwrap();
+        for i in 0..146 {
+            let jd_utc = start + f64::from(i) * 5.0;
+            let date = to_lunar(&ephemeris, jd_utc + 0.5).unwrap();
+            let (_, jd) = from_lunar(
+                &ephemeris,
+                date.month.year,
+                date.month.month,
+                date.day,
+                date.month.leap,
+            )
+            .unwrap();
+            assert_eq!(china_day(jd_utc), china_day(jd));
+        }
+    }
+
+    fn date_key(date: &LunarDate) -> (i32, u8, bool, u8) {
+        (date.month.year, date.month.month, date.month.leap, date.day)
+    }
+}
@@ -0,0 +1,155 @@
+use serde::Serialize;
+use swe::{swe_degnorm, Body};
+
+use crate::{
+    ephemeris::Ephemeris,
+    error::Error,
+    search::{angle_diff, bisect},
+};
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// 平均朔望月，单位：日
+const SYNODIC_MONTH: f64 = 29.530588861;
+
+/// Brown月相数953的平朔，即Meeus《天文算法》中k=0的平朔，2000-01-06约14:20 TT
+/// 实际的朔为2000-01-06 18:14 UT，只用于估算月相数
+const NEW_MOON_953: f64 = 2451550.09766;
+
+/// 月相
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum Phase {
+    /// 朔
+    NewMoon,
+    /// 上弦
+    FirstQuarter,
+    /// 望
+    FullMoon,
+    /// 下弦
+    LastQuarter,
+}
+
+impl Phase {
+    /// 月相对应的月亮与太阳的黄经差
+    pub fn elongation(&self) -> f64 {
+        match self {
+            Phase::NewMoon => 0.0,
+            Phase::FirstQuarter => 90.0,
+            Phase::FullMoon => 180.0,
+            Phase::LastQuarter => 270.0,
+        }
+    }
+}
+
+/// 月亮与太阳的黄经差，即/new_moon的曲线
+pub fn elongation(ephemeris: &Ephemeris, jd_utc: f64) -> Result<f64, Error> {
+    // 太阳、月亮只设置一次星历表
+    let xx = ephemeris.calc_bodies(jd_utc, &[&Body::SeSun, &Body::SeMoon])?;
+    Ok(swe_degnorm(xx[1][0] - xx[0][0]))
+}
+
+/// 求[start, end]内的月相时刻，按时间排序
+pub fn lunar_phases(
+    ephemeris: &Ephemeris,
+    start: f64,
+    end: f64,
+    phases: &[Phase],
+) -> Result<Vec<(Phase, f64)>, Error> {
+    let f = |jd| elongation(ephemeris, jd);
+
+    // 黄经差每日增加约12度，以1日为步长，各月相共用同一组采样
+    let mut events = vec![];
+    let mut jd0 = start;
+    let mut angle0 = f(jd0)?;
+    while jd0 < end {
+        let jd1 = (jd0 + 1.0).min(end);
+        let angle1 = f(jd1)?;
+        for phase in phases {
+            let target = phase.elongation();
+            let (y0, y1) = (angle_diff(angle0, target), angle_diff(angle1, target));
+            // 黄经差只增不减，角度差由负变为非负即越过target；由180跳变到-180时，不是根
+            if y0 < 0.0 && y1 >= 0.0 && y1 - y0 < 180.0 {
+                let jd = bisect(|jd| Ok(angle_diff(f(jd)?, target)), jd0, jd1)?;
+                events.push((*phase, jd));
+            }
+        }
+        jd0 = jd1;
+        angle0 = angle1;
+    }
+    events.sort_by(|a, b| a.1.total_cmp(&b.1));
+    Ok(events)
+}
+
+/// Brown月相数，1923-01-17的新月为第1个
+/// 上弦、望、下弦属于其前一个朔的月相数
+pub fn lunation_number(phase: Phase, jd_utc: f64) -> i32 {
+    let k = (jd_utc - NEW_MOON_953) / SYNODIC_MONTH - phase.elongation() / 360.0;
+    // 真月相与平月相可相差约14小时，但远小于半个朔望月，四舍五入即可
+    k.round() as i32 + 953
+}
+
+#[cfg(test)]
+mod test {
+    use super::{lunar_phases, lunation_number, Phase};
+    use crate::test_util::moshier;
+
+    #[test]
+    fn test_lunation_number() {
+        // 2000-01-06 18:14 UT 新月
+        assert_eq!(953, lunation_number(Phase::NewMoon, 2451550.26));
+        // 2000-01-14 13:34 UT 上弦
+        assert_eq!(953, lunation_number(Phase::FirstQuarter, 2451558.07));
+        // 2000-01-21 04:40 UT 满月
+        assert_eq!(953, lunation_number(Phase::FullMoon, 2451564.69));
+        // 2000-01-28 07:57 UT 下弦
+        assert_eq!(953, lunation_number(Phase::LastQuarter, 2451571.83));
+        // 2000-02-05 13:03 UT 新月
+        assert_eq!(954, lunation_number(Phase::NewMoon, 2451580.04));
+        // 1923-01-17 02:41 UT 新月
+        assert_eq!(1, lunation_number(Phase::NewMoon, 2423436.61));
+    }
+
+    #[test]
+    fn test_lunar_phases() {
+        let ephemeris = moshier(vec![]);
+
+        // 2024-04-05至2024-05-05（UTC）
+        // 朔04-08 18:21，上弦04-15 19:13，望04-23 23:49，下弦05-01 11:27
+        let all = [
+            Phase::NewMoon,
+            Phase::FirstQuarter,
+            Phase::FullMoon,
+            Phase::LastQuarter,
+        ];
+        let phases = lunar_phases(&ephemeris, 2460405.5, 2460435.5, &all).unwrap();
+        let expected = [
+            (Phase::NewMoon, 2460409.26458),
+            (Phase::FirstQuarter, 2460416.30069),
+            (Phase::FullMoon, 2460424.49236),
+            (Phase::LastQuarter, 2460431.97708),
+        ];
+        assert_eq!(expected.len(), phases.len());
+        for ((phase, jd), (expected_phase, expected_jd)) in phases.iter().zip(expected) {
+            assert_eq!(expected_phase, *phase);
+            assert!((jd - expected_jd).abs() < 2.0 / 1440.0, "{jd}");
+            // 同属月相数1253
+            assert_eq!(1253, lunation_number(*phase, *jd));
+        }
+
+        // 只求朔、望
+        let phases = lunar_phases(
+            &ephemeris,
+            2460405.5,
+            2460435.5,
+            &[Phase::NewMoon, Phase::FullMoon],
+        )
+        .unwrap();
+        assert_eq!(
+            vec![Phase::NewMoon, Phase::FullMoon],
+            phases.iter().map(|(phase, _)| *phase).collect::<Vec<_>>()
+        );
+    }
+}
@@ -5,7 +5,11 @@ use std::{env, net::SocketAddrV4};
 use actix_cors::Cors;
 
 use clap::Parser;
-use sun_moon::{args, routers::api_routes, state::AppState};
+use sun_moon::{
+    args,
+    routers::api_routes,
+    state::{AppState, Backend},
+};
 
 #[cfg(feature = "swagger")]
 use sun_moon::swagger::ApiDoc;
@@ -25,10 +29,39 @@ async fn main() -> std::io::Result<()> {
 
     log4rs::init_file(log4rs_config, Default::default()).unwrap();
 
-    let ephe_path = env::var("EPHE_PATH")
-        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
-
-    let shared_data = web::Data::new(AppState { ephe_path });
+    // 星历表，默认swiss
+    let backend: Backend = env::var("EPHE_BACKEND")
+        .unwrap_or("swiss".to_string())
+        .parse()
+        .expect("EPHE_BACKEND 环境变量错误，只能为swiss、moshier、jpl");
+
+    // Moshier星历表无需星历表文件
+    let ephe_path = if backend == Backend::Moshier {
+        env::var("EPHE_PATH").unwrap_or_default()
+    } else {
+        env::var("EPHE_PATH")
+            .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...")
+    };
+
+    let jpl_file = if backend == Backend::Jpl {
+        let jpl_file = env::var("JPL_FILE")
+            .expect("没设置 JPL_FILE 环境变量，可在.env文件中设置或export JPL_FILE=...");
+        Some(jpl_file)
+    } else {
+        None
+    };
+
+    // 星历表文件计算失败时，是否改用Moshier星历表，默认false
+    let fallback = env::var("EPHE_FALLBACK")
+        .map(|v| v == "true" || v == "1")
+        .unwrap_or(false);
+
+    let shared_data = web::Data::new(AppState {
+        ephe_path,
+        backend,
+        jpl_file,
+        fallback,
+    });
 
     let args = args::Args::parse();
 
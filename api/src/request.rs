@@ -2,7 +2,9 @@ use serde::Deserialize;
 #[cfg(feature = "swagger")]
 use utoipa::ToSchema;
 
-use validator::Validate;
+use validator::{Validate, ValidationError};
+
+use crate::{aspect::MAJOR_ASPECTS, eclipse::EclipseKind};
 
 #[derive(Deserialize, Validate)]
 #[cfg_attr(feature = "swagger", derive(ToSchema))]
@@ -34,4 +36,502 @@ pub struct DateRangeRequest {
     pub start: DateRequest,
     #[validate]
     pub end: DateRequest,
+    /// 输出格式，默认compact
+    #[serde(default)]
+    pub output: Output,
+    /// 坐标系，默认ecliptic
+    /// 新月黄道经度仅可用ecliptic
+    #[serde(default)]
+    pub coordinate: Coordinate,
+    /// 中心，默认geocentric
+    #[serde(default)]
+    pub frame: Frame,
+    /// 观测者位置，给出时计算站心坐标，否则计算地心坐标
+    /// 仅frame=geocentric时可用
+    #[validate]
+    pub observer: Option<Observer>,
+    /// 黄道带，默认tropical
+    #[serde(default)]
+    pub zodiac: Zodiac,
+    /// 岁差，默认lahiri，zodiac=sidereal时使用
+    #[serde(default)]
+    pub ayanamsa: Ayanamsa,
+    /// 位置选项，默认为当日视位置
+    #[serde(default)]
+    pub position: PositionOptions,
+}
+
+/// 查找给定区间内的天象
+/// 其余区间请求以#[serde(flatten)]包含此结构
+#[derive(Deserialize, Validate)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct EventRequest {
+    #[validate]
+    pub start: DateRequest,
+    #[validate]
+    pub end: DateRequest,
+    /// 时区，东为正，西为负，默认8
+    #[serde(default = "default_tz")]
+    #[validate(range(min = -12.0, max = 12.0, message = "-12<=时区<=12"))]
+    pub tz: f64,
+}
+
+fn default_tz() -> f64 {
+    8.0
+}
+
+/// 时间
+#[derive(Deserialize, Validate)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct DateTimeRequest {
+    #[validate]
+    pub date: DateRequest,
+    /// 时区，东为正，西为负，默认8
+    #[serde(default = "default_tz")]
+    #[validate(range(min = -12.0, max = 12.0, message = "-12<=时区<=12"))]
+    pub tz: f64,
+    /// 地理经度，东经为正，西经为负
+    /// 用于计算地方平太阳时、真太阳时
+    /// 干支四柱给出时，以真太阳时的23时为界换日（子初换日），否则以钟表时间的23时为界
+    #[validate(range(min = -180.0, max = 180.0, message = "-180<=经度<=180"))]
+    pub long: Option<f64>,
+}
+
+/// 农历日期
+#[derive(Deserialize, Validate)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct LunarDateRequest {
+    /// 农历年，以正月所在的公历年表示，最小值1900
+    #[validate(range(min = 1900, message = "农历年最小1900"))]
+    pub year: i32,
+    /// 月
+    #[validate(range(min = 1, max = 12, message = "1<=月份<=12"))]
+    pub month: u8,
+    /// 日
+    #[validate(range(min = 1, max = 30, message = "1<=日期<=30"))]
+    pub day: u8,
+    /// 是否为闰月，默认false
+    #[serde(default)]
+    pub leap: bool,
+    /// 输出公历日期的时区，东为正，西为负，默认8
+    #[serde(default = "default_tz")]
+    #[validate(range(min = -12.0, max = 12.0, message = "-12<=时区<=12"))]
+    pub tz: f64,
+}
+
+/// 查找两星体的相位
+#[derive(Deserialize, Validate)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct AspectRequest {
+    /// 起止时间及时区，同EventRequest
+    #[serde(flatten)]
+    #[validate]
+    pub range: EventRequest,
+    /// 相位，单位：度，默认为合、六合、刑、拱、冲：[0, 60, 90, 120, 180]
+    #[serde(default = "default_aspects")]
+    pub angles: Vec<f64>,
+    /// 容许度，与精确相位之差不超过此值时输出，单位：度，默认8
+    #[serde(default = "default_orb")]
+    #[validate(range(min = 0.0, max = 30.0, message = "0<=orb<=30"))]
+    pub orb: f64,
+}
+
+fn default_aspects() -> Vec<f64> {
+    MAJOR_ASPECTS.to_vec()
+}
+
+fn default_orb() -> f64 {
+    8.0
+}
+
+/// 查找月亮过近地点、远地点
+#[derive(Deserialize, Validate)]
+#[validate(schema(function = "validate_apsis_request"))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct ApsisRequest {
+    /// 起止时间及时区，同EventRequest
+    #[serde(flatten)]
+    #[validate]
+    pub range: EventRequest,
+    /// 朔、望时月地距离不超过此值为超级月亮，单位：千米，默认360000
+    #[serde(default = "default_supermoon")]
+    #[validate(range(min = 350000.0, max = 410000.0, message = "350000<=supermoon<=410000"))]
+    pub supermoon: f64,
+    /// 朔、望时月地距离不小于此值为微型月亮，单位：千米，默认405000
+    #[serde(default = "default_micromoon")]
+    #[validate(range(min = 350000.0, max = 410000.0, message = "350000<=micromoon<=410000"))]
+    pub micromoon: f64,
+}
+
+fn default_supermoon() -> f64 {
+    360000.0
+}
+
+fn default_micromoon() -> f64 {
+    405000.0
+}
+
+/// 超级月亮的距离须小于微型月亮的距离
+fn validate_apsis_request(r: &ApsisRequest) -> Result<(), ValidationError> {
+    validate_moon_distances(r.supermoon, r.micromoon)
+}
+
+fn validate_moon_distances(supermoon: f64, micromoon: f64) -> Result<(), ValidationError> {
+    if supermoon < micromoon {
+        Ok(())
+    } else {
+        let mut e = ValidationError::new("supermoon_micromoon");
+        e.message = Some("supermoon须小于micromoon".into());
+        Err(e)
+    }
+}
+
+/// 年份区间
+#[derive(Deserialize, Validate)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct YearRangeRequest {
+    /// 起始年，最小值1900
+    #[validate(range(min = 1900, message = "起始年最小1900"))]
+    pub start_year: i32,
+    /// 终止年（含），最小值1900
+    #[validate(range(min = 1900, message = "终止年最小1900"))]
+    pub end_year: i32,
+    /// 时区，东为正，西为负，默认8
+    #[serde(default = "default_tz")]
+    #[validate(range(min = -12.0, max = 12.0, message = "-12<=时区<=12"))]
+    pub tz: f64,
+}
+
+/// 观测者所在地的升落
+#[derive(Deserialize, Validate)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct RiseSetRequest {
+    /// 起止日期及时区，同EventRequest
+    /// 仅用年月日，含终止日期，按tz的日期逐日计算
+    #[serde(flatten)]
+    #[validate]
+    pub range: EventRequest,
+    #[validate]
+    pub observer: Observer,
+    /// 星体名称，同/body/{name}，默认["sun", "moon"]
+    /// 月交点、月远地点及地球没有升落
+    #[serde(default = "default_rise_set_bodies")]
+    pub bodies: Vec<String>,
+    /// 是否计大气折射，默认true
+    #[serde(default = "default_refraction")]
+    pub refraction: bool,
+    /// 以日面、月面的上缘、中心或下缘为准，默认上缘
+    #[serde(default)]
+    pub disc: Disc,
+    /// 极昼、极夜等不升不落之日的处理，默认标记
+    #[serde(default)]
+    pub polar: Polar,
+}
+
+fn default_rise_set_bodies() -> Vec<String> {
+    vec!["sun".to_string(), "moon".to_string()]
+}
+
+fn default_refraction() -> bool {
+    true
+}
+
+/// 观测者所在地的晨昏蒙影
+#[derive(Deserialize, Validate)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct TwilightRequest {
+    /// 起止日期及时区，同EventRequest
+    /// 仅用年月日，含终止日期，按tz的日期逐日计算
+    #[serde(flatten)]
+    #[validate]
+    pub range: EventRequest,
+    #[validate]
+    pub observer: Observer,
+    /// 自定义的太阳中心高度，单位：度，-90<=高度<=90
+    /// 民用（-6度）、航海（-12度）、天文（-18度）晨昏蒙影总是输出
+    #[serde(default)]
+    #[validate(custom = "validate_altitudes")]
+    pub altitudes: Vec<f64>,
+}
+
+fn validate_altitudes(altitudes: &[f64]) -> Result<(), ValidationError> {
+    if altitudes
+        .iter()
+        .all(|altitude| (-90.0..=90.0).contains(altitude))
+    {
+        Ok(())
+    } else {
+        let mut e = ValidationError::new("altitudes");
+        e.message = Some("-90<=太阳高度<=90".into());
+        Err(e)
+    }
+}
+
+/// 观测者所在地的日食、月食
+#[derive(Deserialize, Validate)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct LocalEclipseRequest {
+    /// 自此时间起，查找下一次日食或月食
+    #[validate]
+    pub date: DateRequest,
+    /// 时区，东为正，西为负，默认8
+    #[serde(default = "default_tz")]
+    #[validate(range(min = -12.0, max = 12.0, message = "-12<=时区<=12"))]
+    pub tz: f64,
+    pub kind: EclipseKind,
+    #[validate]
+    pub observer: Observer,
+}
+
+/// 查找黄道附近的恒星
+#[derive(Deserialize, Validate)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct StarsRequest {
+    #[validate]
+    pub date: DateRequest,
+    /// 距黄道的最大角距离，即黄道纬度绝对值的最大值，单位：度
+    #[validate(range(min = 0.0, max = 90.0, message = "0<=max_lat<=90"))]
+    pub max_lat: f64,
+}
+
+/// 输出格式
+#[derive(Deserialize, Clone, Copy, Default, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum Output {
+    /// 仅输出黄道经度
+    #[default]
+    Compact,
+    /// 输出黄道经度、黄道纬度、距离及其每日速度
+    Full,
+}
+
+/// 坐标系
+#[derive(Deserialize, Clone, Copy, Default, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum Coordinate {
+    /// 黄道坐标，输出黄道经度、黄道纬度
+    #[default]
+    Ecliptic,
+    /// 当日赤道坐标，输出赤经、赤纬
+    Equatorial,
+    /// J2000赤道坐标，输出赤经、赤纬
+    EquatorialJ2000,
+}
+
+/// 坐标中心
+#[derive(Deserialize, Clone, Copy, Default, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum Frame {
+    /// 地心
+    #[default]
+    Geocentric,
+    /// 日心
+    Heliocentric,
+    /// 太阳系质心
+    Barycentric,
+}
+
+/// 黄道带
+#[derive(Deserialize, Clone, Copy, Default, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum Zodiac {
+    /// 回归黄道
+    #[default]
+    Tropical,
+    /// 恒星黄道
+    Sidereal,
+}
+
+/// 岁差，即瑞士星历表的sid mode
+/// 值与SE_SIDM_*相同
+#[derive(Deserialize, Clone, Copy, Default, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum Ayanamsa {
+    /// Fagan/Bradley，西方恒星占星
+    FaganBradley = 0,
+    /// Lahiri，印度政府采用
+    #[default]
+    Lahiri = 1,
+    /// De Luce
+    Deluce = 2,
+    /// B. V. Raman
+    Raman = 3,
+    /// Usha/Shashi
+    Ushashashi = 4,
+    /// Krishnamurti，KP占星
+    Krishnamurti = 5,
+    /// Djwhal Khul
+    DjwhalKhul = 6,
+    /// Sri Yukteshwar
+    Yukteshwar = 7,
+    /// J. N. Bhasin
+    JnBhasin = 8,
+    /// 巴比伦，Kugler 1
+    BabylKugler1 = 9,
+    /// 巴比伦，Kugler 2
+    BabylKugler2 = 10,
+    /// 巴比伦，Kugler 3
+    BabylKugler3 = 11,
+    /// 巴比伦，Huber
+    BabylHuber = 12,
+    /// 巴比伦，以双鱼座η为准
+    BabylEtpsc = 13,
+    /// 毕宿五位于金牛15度
+    Aldebaran15Tau = 14,
+    /// 喜帕恰斯
+    Hipparchos = 15,
+    /// 萨珊王朝
+    Sassanian = 16,
+    /// 银河中心位于射手0度
+    Galcent0Sag = 17,
+    /// J2000历元，岁差为0
+    J2000 = 18,
+    /// J1900历元，岁差为0
+    J1900 = 19,
+    /// B1950历元，岁差为0
+    B1950 = 20,
+    /// 苏利耶历数书
+    Suryasiddhanta = 21,
+    /// 苏利耶历数书，以平太阳为准
+    SuryasiddhantaMsun = 22,
+    /// 阿耶波多
+    Aryabhata = 23,
+    /// 阿耶波多，以平太阳为准
+    AryabhataMsun = 24,
+    /// 苏利耶历数书，以Revati（外屏七）为准
+    SsRevati = 25,
+    /// 苏利耶历数书，以Citra（角宿一）为准
+    SsCitra = 26,
+    /// 角宿一位于天秤0度
+    TrueCitra = 27,
+    /// Revati（外屏七）位于双鱼29度50分
+    TrueRevati = 28,
+    /// Pushya（鬼宿三）位于巨蟹16度
+    TruePushya = 29,
+    /// 银河中心，R. Gil Brand
+    GalcentRgilbrand = 30,
+    /// 银道面，IAU 1958
+    GalequIau1958 = 31,
+    /// 银道面，真位置
+    GalequTrue = 32,
+    /// 银道面，位于Mula中点
+    GalequMula = 33,
+    /// 银河中心与银道面对齐，Skydram/Mardyks
+    GalalignMardyks = 34,
+    /// Mula（尾宿九）位于射手0度
+    TrueMula = 35,
+    /// 银河中心位于Mula中点，Wilhelm
+    GalcentMulaWilhelm = 36,
+    /// 阿耶波多，公元522年
+    Aryabhata522 = 37,
+    /// 巴比伦，Britton
+    BabylBritton = 38,
+    /// Vedic，Sheoran
+    TrueSheoran = 39,
+    /// 银河中心位于摩羯0度，Cochrane
+    GalcentCochrane = 40,
+    /// 银道面，Fiorenza
+    GalequFiorenza = 41,
+    /// Vettius Valens，以月亮为准
+    ValensMoon = 42,
+    /// Lahiri，1940年
+    Lahiri1940 = 43,
+    /// Lahiri，春分点于285年
+    LahiriVp285 = 44,
+    /// Krishnamurti，春分点于291年
+    KrishnamurtiVp291 = 45,
+    /// Lahiri，印度历法改革委员会
+    LahiriIcrc = 46,
+}
+
+/// 位置选项
+/// 默认计算当日视位置，即含光行时、光行差、光线偏折、章动
+#[derive(Deserialize, Clone, Copy, Default, PartialEq, Debug)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct PositionOptions {
+    /// 不计光行差
+    #[serde(default)]
+    pub no_aberration: bool,
+    /// 不计光行时，即几何位置
+    ///
+    /// 对应SEFLG_TRUEPOS，同时不计光行差与光线偏折，no_aberration、no_deflection不再起作用
+    #[serde(default)]
+    pub no_light_time: bool,
+    /// 不计光线偏折
+    #[serde(default)]
+    pub no_deflection: bool,
+    /// 不计章动，即平位置
+    #[serde(default)]
+    pub no_nutation: bool,
+    /// J2000坐标，不能与zodiac=sidereal同时使用
+    #[serde(default)]
+    pub j2000: bool,
+    /// ICRS坐标
+    #[serde(default)]
+    pub icrs: bool,
+}
+
+/// 观测者的地理位置
+#[derive(Deserialize, Validate, Clone, Copy, Debug)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct Observer {
+    /// 地理纬度，北纬为正，南纬为负
+    #[validate(range(min = -90.0, max = 90.0, message = "-90<=纬度<=90"))]
+    pub lat: f64,
+    /// 地理经度，东经为正，西经为负
+    #[validate(range(min = -180.0, max = 180.0, message = "-180<=经度<=180"))]
+    pub long: f64,
+    /// 海拔，单位：米，默认0
+    #[serde(default)]
+    pub alt: f64,
+}
+
+/// 升落以日面、月面的哪一部分为准
+#[derive(Deserialize, Clone, Copy, Default, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum Disc {
+    /// 上缘
+    #[default]
+    UpperLimb,
+    /// 中心
+    Center,
+    /// 下缘
+    LowerLimb,
+}
+
+/// 极昼、极夜等不升不落之日的处理
+#[derive(Deserialize, Clone, Copy, Default, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum Polar {
+    /// 输出该日，标记为终日在地平之上或之下
+    #[default]
+    Flag,
+    /// 略去该日
+    Skip,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{validate_altitudes, validate_moon_distances};
+
+    #[test]
+    fn test_validate_moon_distances() {
+        assert!(validate_moon_distances(360000.0, 405000.0).is_ok());
+        assert!(validate_moon_distances(405000.0, 405000.0).is_err());
+        assert!(validate_moon_distances(406000.0, 360000.0).is_err());
+    }
+
+    #[test]
+    fn test_validate_altitudes() {
+        assert!(validate_altitudes(&[]).is_ok());
+        assert!(validate_altitudes(&[-90.0, -0.833, 90.0]).is_ok());
+        assert!(validate_altitudes(&[-6.0, -91.0]).is_err());
+    }
 }
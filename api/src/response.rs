@@ -1,6 +1,20 @@
 use serde::Serialize;
 
-use crate::horo_date_time::HoroDateTime;
+use crate::{
+    apsis::{Apsis, ApsisKind, Syzygy},
+    aspect::{Aspect, AspectSample},
+    eclipse::{Contact, Eclipse, EclipseKind, EclipseType, LocalEclipse},
+    error::Error,
+    ganzhi::{FourPillars, Pillar},
+    horo_date_time::HoroDateTime,
+    ingress::{Ingress, VoidOfCourse, SIGN_NAMES},
+    lunar_calendar::LunarDate,
+    lunar_phase::Phase,
+    rise_set::{HorizonStatus, RiseSet, Twilight, TwilightKind},
+    solar_term::{Seasons, SolarTerm},
+    solar_time::SolarTime,
+    station::{Station, StationKind},
+};
 
 #[cfg(feature = "swagger")]
 use utoipa::ToSchema;
@@ -9,11 +23,750 @@ use utoipa::ToSchema;
 #[cfg_attr(feature = "swagger", derive(ToSchema))]
 pub struct LongResponser {
     date: HoroDateTime,
+    /// 黄道经度，赤道坐标时为赤经，单位：度
     long: f64,
+    /// 黄道纬度，赤道坐标时为赤纬，output=full时输出
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lat: Option<f64>,
+    /// 距离，单位：AU，output=full时输出
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dist: Option<f64>,
+    /// 经度速度，单位：度/日，output=full时输出
+    #[serde(skip_serializing_if = "Option::is_none")]
+    long_speed: Option<f64>,
+    /// 纬度速度，单位：度/日，output=full时输出
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lat_speed: Option<f64>,
+    /// 距离速度，单位：AU/日，output=full时输出
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dist_speed: Option<f64>,
+    /// 观测者所在地的地方平太阳时、真太阳时，给出observer时输出
+    #[serde(skip_serializing_if = "Option::is_none")]
+    solar_time: Option<SolarTimeResponser>,
 }
 
 impl LongResponser {
     pub fn new(date: HoroDateTime, long: f64) -> Self {
-        Self { date, long }
+        Self {
+            date,
+            long,
+            lat: None,
+            dist: None,
+            long_speed: None,
+            lat_speed: None,
+            dist_speed: None,
+            solar_time: None,
+        }
+    }
+
+    /// 由swe_calc_ut()的计算结果构造完整输出
+    pub fn from_xx(date: HoroDateTime, xx: &[f64; 6]) -> Self {
+        Self {
+            date,
+            long: xx[0],
+            lat: Some(xx[1]),
+            dist: Some(xx[2]),
+            long_speed: Some(xx[3]),
+            lat_speed: Some(xx[4]),
+            dist_speed: Some(xx[5]),
+            solar_time: None,
+        }
+    }
+
+    /// 设置黄道纬度
+    pub fn with_lat(mut self, lat: f64) -> Self {
+        self.lat = Some(lat);
+        self
+    }
+
+    /// 设置黄道经度速度
+    pub fn with_long_speed(mut self, long_speed: f64) -> Self {
+        self.long_speed = Some(long_speed);
+        self
+    }
+
+    /// 设置地方平太阳时、真太阳时
+    pub fn with_solar_time(mut self, solar_time: Option<SolarTimeResponser>) -> Self {
+        self.solar_time = solar_time;
+        self
+    }
+}
+
+/// 恒星的位置
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct StarResponser {
+    /// 星表中的恒星全名，如：Spica,alVir
+    name: String,
+    /// 黄道经度，单位：度
+    long: f64,
+    /// 黄道纬度，单位：度
+    lat: f64,
+}
+
+impl StarResponser {
+    pub fn new(name: String, long: f64, lat: f64) -> Self {
+        Self { name, long, lat }
+    }
+}
+
+/// 朔望
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct LunationResponser {
+    phase: Phase,
+    /// Brown月相数
+    lunation: i32,
+    /// UTC时的儒略日
+    jd_utc: f64,
+    /// UTC时间
+    utc: HoroDateTime,
+    /// 请求时区的时间
+    date: HoroDateTime,
+}
+
+impl LunationResponser {
+    pub fn new(phase: Phase, lunation: i32, jd_utc: f64, tz: f64) -> Result<Self, Error> {
+        Ok(Self {
+            phase,
+            lunation,
+            jd_utc,
+            utc: HoroDateTime::from_jd_zone(jd_utc, 0.0)?,
+            date: HoroDateTime::from_jd_zone(jd_utc, tz)?,
+        })
+    }
+}
+
+/// 节气
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct SolarTermResponser {
+    /// 节气名，如：立春
+    name: &'static str,
+    /// 是否为中气，否则为节
+    zhong_qi: bool,
+    /// 太阳黄经，单位：度
+    long: f64,
+    /// UTC时的儒略日
+    jd_utc: f64,
+    /// 请求时区的时间
+    date: HoroDateTime,
+}
+
+impl SolarTermResponser {
+    pub fn new(term: &SolarTerm, tz: f64) -> Result<Self, Error> {
+        Ok(Self {
+            name: term.name(),
+            zhong_qi: term.is_zhong_qi(),
+            long: term.long(),
+            jd_utc: term.jd_utc,
+            date: HoroDateTime::from_jd_zone(term.jd_utc, tz)?,
+        })
+    }
+}
+
+/// 农历日期
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct LunarDateResponser {
+    /// 农历年，以正月所在的公历年表示
+    year: i32,
+    /// 月
+    month: u8,
+    /// 日
+    day: u8,
+    /// 是否为闰月
+    leap: bool,
+    /// 本月天数，29或30
+    month_days: u8,
+    /// 月名，如：闰四月
+    month_name: String,
+    /// 日名，如：初一
+    day_name: &'static str,
+    /// 公历时间
+    date: HoroDateTime,
+}
+
+impl LunarDateResponser {
+    pub fn new(lunar: &LunarDate, date: HoroDateTime) -> Self {
+        Self {
+            year: lunar.month.year,
+            month: lunar.month.month,
+            day: lunar.day,
+            leap: lunar.month.leap,
+            month_days: lunar.month.days,
+            month_name: lunar.month_name(),
+            day_name: lunar.day_name(),
+            date,
+        }
+    }
+}
+
+/// 干支
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct PillarResponser {
+    /// 天干序号，0为甲
+    stem: usize,
+    /// 地支序号，0为子
+    branch: usize,
+    /// 干支名，如：甲子
+    name: String,
+}
+
+impl From<Pillar> for PillarResponser {
+    fn from(value: Pillar) -> Self {
+        Self {
+            stem: value.stem,
+            branch: value.branch,
+            name: value.name(),
+        }
+    }
+}
+
+/// 四柱
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct FourPillarsResponser {
+    year: PillarResponser,
+    month: PillarResponser,
+    day: PillarResponser,
+    hour: PillarResponser,
+    /// 本月起始的节
+    month_start: SolarTermResponser,
+    /// 下月起始的节
+    month_end: SolarTermResponser,
+    date: HoroDateTime,
+}
+
+impl FourPillarsResponser {
+    /// tz: 节的时区
+    pub fn new(pillars: &FourPillars, date: HoroDateTime, tz: f64) -> Result<Self, Error> {
+        Ok(Self {
+            year: pillars.year.into(),
+            month: pillars.month.into(),
+            day: pillars.day.into(),
+            hour: pillars.hour.into(),
+            month_start: SolarTermResponser::new(&pillars.month_start, tz)?,
+            month_end: SolarTermResponser::new(&pillars.month_end, tz)?,
+            date,
+        })
+    }
+}
+
+/// 地方平太阳时、真太阳时
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct SolarTimeResponser {
+    /// 地方平太阳时
+    lmt: HoroDateTime,
+    /// 真太阳时
+    true_solar_time: HoroDateTime,
+    /// 时差，真太阳时-平太阳时，单位：分钟
+    equation_of_time: f64,
+}
+
+impl From<SolarTime> for SolarTimeResponser {
+    fn from(value: SolarTime) -> Self {
+        Self {
+            lmt: value.lmt,
+            true_solar_time: value.true_solar_time,
+            equation_of_time: value.equation_of_time,
+        }
+    }
+}
+
+/// 时间及其地方平太阳时、真太阳时
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct SolarTimeConversionResponser {
+    /// 钟表时间
+    date: HoroDateTime,
+    /// 地理经度
+    long: f64,
+    #[serde(flatten)]
+    solar_time: SolarTimeResponser,
+}
+
+impl SolarTimeConversionResponser {
+    pub fn new(date: HoroDateTime, long: f64, solar_time: SolarTime) -> Self {
+        Self {
+            date,
+            long,
+            solar_time: solar_time.into(),
+        }
+    }
+}
+
+/// 日食、月食
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct EclipseResponser {
+    kind: EclipseKind,
+    eclipse_type: EclipseType,
+    /// 食分
+    magnitude: f64,
+    /// 沙罗序列号
+    saros: i32,
+    /// 在沙罗序列中的序号
+    saros_member: i32,
+    /// 食甚，UTC时的儒略日
+    jd_utc: f64,
+    /// 食甚，请求时区的时间
+    date: HoroDateTime,
+}
+
+impl EclipseResponser {
+    pub fn new(eclipse: &Eclipse, tz: f64) -> Result<Self, Error> {
+        Ok(Self {
+            kind: eclipse.kind,
+            eclipse_type: eclipse.eclipse_type,
+            magnitude: eclipse.magnitude,
+            saros: eclipse.saros,
+            saros_member: eclipse.saros_member,
+            jd_utc: eclipse.jd_utc,
+            date: HoroDateTime::from_jd_zone(eclipse.jd_utc, tz)?,
+        })
+    }
+}
+
+/// 观测者所在地的交食时刻
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct ContactResponser {
+    /// 名称，日食为c1-c4（初亏、食既、生光、复圆），月食为p1、u1-u4、p4（半影食始、初亏、食既、生光、复圆、半影食终），max为食甚
+    name: &'static str,
+    /// UTC时的儒略日
+    jd_utc: f64,
+    /// 请求时区的时间
+    date: HoroDateTime,
+    /// 太阳或月亮的真高度，单位：度
+    altitude: f64,
+    /// 计大气折射后，太阳或月亮是否在地平线上
+    visible: bool,
+}
+
+impl ContactResponser {
+    pub fn new(contact: &Contact, tz: f64) -> Result<Self, Error> {
+        Ok(Self {
+            name: contact.name,
+            jd_utc: contact.jd_utc,
+            date: HoroDateTime::from_jd_zone(contact.jd_utc, tz)?,
+            altitude: contact.altitude,
+            visible: contact.visible,
+        })
+    }
+}
+
+/// 观测者所在地的日食、月食
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct LocalEclipseResponser {
+    /// 全球的交食
+    eclipse: EclipseResponser,
+    /// 当地是否可见
+    visible: bool,
+    /// 当地所见的食的类型
+    #[serde(skip_serializing_if = "Option::is_none")]
+    eclipse_type: Option<EclipseType>,
+    /// 当地的食分
+    magnitude: f64,
+    /// 当地食甚时太阳面积被遮住的比例，仅用于日食
+    #[serde(skip_serializing_if = "Option::is_none")]
+    obscuration: Option<f64>,
+    /// 当地的食甚
+    #[serde(skip_serializing_if = "Option::is_none")]
+    maximum: Option<ContactResponser>,
+    /// 初亏、复圆等时刻，按时间排序
+    contacts: Vec<ContactResponser>,
+}
+
+impl LocalEclipseResponser {
+    pub fn new(local: &LocalEclipse, tz: f64) -> Result<Self, Error> {
+        Ok(Self {
+            eclipse: EclipseResponser::new(&local.eclipse, tz)?,
+            visible: local.is_visible(),
+            eclipse_type: local.eclipse_type,
+            magnitude: local.magnitude,
+            obscuration: local.obscuration,
+            maximum: local
+                .maximum
+                .as_ref()
+                .map(|contact| ContactResponser::new(contact, tz))
+                .transpose()?,
+            contacts: local
+                .contacts
+                .iter()
+                .map(|contact| ContactResponser::new(contact, tz))
+                .collect::<Result<Vec<_>, Error>>()?,
+        })
+    }
+}
+
+/// 入宫
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct IngressResponser {
+    /// 进入的宫，如：白羊
+    sign: &'static str,
+    /// 越过的宫界的黄经，单位：度
+    long: f64,
+    /// 是否逆行入宫
+    retrograde: bool,
+    /// UTC时的儒略日
+    jd_utc: f64,
+    /// 请求时区的时间
+    date: HoroDateTime,
+}
+
+impl IngressResponser {
+    pub fn new(ingress: &Ingress, tz: f64) -> Result<Self, Error> {
+        Ok(Self {
+            sign: ingress.sign_name(),
+            long: ingress.long(),
+            retrograde: ingress.retrograde,
+            jd_utc: ingress.jd_utc,
+            date: HoroDateTime::from_jd_zone(ingress.jd_utc, tz)?,
+        })
+    }
+}
+
+/// 月亮空亡
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct VoidOfCourseResponser {
+    /// 开始，即月亮与行星的最后一个主要相位，请求时区的时间
+    start: HoroDateTime,
+    /// 结束，即月亮入宫，请求时区的时间
+    end: HoroDateTime,
+    /// 结束时进入的宫，如：白羊
+    sign: &'static str,
+    /// 最后一个主要相位的行星，上一宫内没有主要相位时省略，空亡自入上一宫起
+    #[serde(skip_serializing_if = "Option::is_none")]
+    aspect_body: Option<&'static str>,
+    /// 最后一个主要相位，单位：度
+    #[serde(skip_serializing_if = "Option::is_none")]
+    aspect: Option<f64>,
+}
+
+impl VoidOfCourseResponser {
+    pub fn new(void: &VoidOfCourse, tz: f64) -> Result<Self, Error> {
+        Ok(Self {
+            start: HoroDateTime::from_jd_zone(void.start, tz)?,
+            end: HoroDateTime::from_jd_zone(void.end, tz)?,
+            sign: SIGN_NAMES[void.sign],
+            aspect_body: void.aspect.map(|(body, _)| body),
+            aspect: void.aspect.map(|(_, angle)| angle),
+        })
+    }
+}
+
+/// 影子期
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct ShadowResponser {
+    start: HoroDateTime,
+    end: HoroDateTime,
+}
+
+/// 留
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct StationResponser {
+    /// 行星，如：水星
+    body: &'static str,
+    kind: StationKind,
+    /// 黄道经度，单位：度
+    long: f64,
+    /// UTC时的儒略日
+    jd_utc: f64,
+    /// 请求时区的时间
+    date: HoroDateTime,
+    /// 影子期，顺行转逆行时为前影子期，逆行转顺行时为后影子期
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shadow: Option<ShadowResponser>,
+}
+
+impl StationResponser {
+    pub fn new(station: &Station, tz: f64) -> Result<Self, Error> {
+        let shadow = station
+            .shadow
+            .map(|(start, end)| -> Result<ShadowResponser, Error> {
+                Ok(ShadowResponser {
+                    start: HoroDateTime::from_jd_zone(start, tz)?,
+                    end: HoroDateTime::from_jd_zone(end, tz)?,
+                })
+            })
+            .transpose()?;
+        Ok(Self {
+            body: station.body,
+            kind: station.kind,
+            long: station.long,
+            jd_utc: station.jd_utc,
+            date: HoroDateTime::from_jd_zone(station.jd_utc, tz)?,
+            shadow,
+        })
+    }
+}
+
+/// 精确相位
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct AspectResponser {
+    /// 相位，单位：度
+    angle: f64,
+    /// 第一个星体的黄道经度
+    long1: f64,
+    /// 第二个星体的黄道经度
+    long2: f64,
+    /// UTC时的儒略日
+    jd_utc: f64,
+    /// 请求时区的时间
+    date: HoroDateTime,
+}
+
+impl AspectResponser {
+    pub fn new(aspect: &Aspect, tz: f64) -> Result<Self, Error> {
+        Ok(Self {
+            angle: aspect.angle,
+            long1: aspect.long1,
+            long2: aspect.long2,
+            jd_utc: aspect.jd_utc,
+            date: HoroDateTime::from_jd_zone(aspect.jd_utc, tz)?,
+        })
+    }
+}
+
+/// 某一时刻两星体所成的相位
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct AspectSampleResponser {
+    date: HoroDateTime,
+    /// 相位，单位：度
+    angle: f64,
+    /// 与精确相位之差，单位：度
+    orb: f64,
+    /// 是否为入相位，否则为出相位
+    applying: bool,
+}
+
+impl AspectSampleResponser {
+    pub fn new(sample: &AspectSample, tz: f64) -> Result<Self, Error> {
+        Ok(Self {
+            date: HoroDateTime::from_jd_zone(sample.jd_utc, tz)?,
+            angle: sample.angle,
+            orb: sample.orb,
+            applying: sample.applying,
+        })
+    }
+}
+
+/// 两星体的相位
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct AspectsResponser {
+    /// 精确相位
+    exact: Vec<AspectResponser>,
+    /// 在容许度内的各时刻
+    samples: Vec<AspectSampleResponser>,
+}
+
+impl AspectsResponser {
+    pub fn new(exact: Vec<AspectResponser>, samples: Vec<AspectSampleResponser>) -> Self {
+        Self { exact, samples }
+    }
+}
+
+/// 月亮过近地点或远地点
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct ApsisResponser {
+    kind: ApsisKind,
+    /// 月地距离，单位：千米
+    distance: f64,
+    /// UTC时的儒略日
+    jd_utc: f64,
+    /// 请求时区的时间
+    date: HoroDateTime,
+}
+
+impl ApsisResponser {
+    pub fn new(apsis: &Apsis, tz: f64) -> Result<Self, Error> {
+        Ok(Self {
+            kind: apsis.kind,
+            distance: apsis.distance,
+            jd_utc: apsis.jd_utc,
+            date: HoroDateTime::from_jd_zone(apsis.jd_utc, tz)?,
+        })
+    }
+}
+
+/// 朔、望时的月地距离
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct SyzygyResponser {
+    phase: Phase,
+    /// 月地距离，单位：千米
+    distance: f64,
+    /// 是否为超级月亮
+    supermoon: bool,
+    /// 是否为微型月亮
+    micromoon: bool,
+    /// UTC时的儒略日
+    jd_utc: f64,
+    /// 请求时区的时间
+    date: HoroDateTime,
+}
+
+impl SyzygyResponser {
+    pub fn new(syzygy: &Syzygy, supermoon: f64, micromoon: f64, tz: f64) -> Result<Self, Error> {
+        Ok(Self {
+            phase: syzygy.phase,
+            distance: syzygy.distance,
+            supermoon: syzygy.distance <= supermoon,
+            micromoon: syzygy.distance >= micromoon,
+            jd_utc: syzygy.jd_utc,
+            date: HoroDateTime::from_jd_zone(syzygy.jd_utc, tz)?,
+        })
+    }
+}
+
+/// 月亮近地点、远地点，及朔、望
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct ApsidesResponser {
+    /// 近地点、远地点
+    apsides: Vec<ApsisResponser>,
+    /// 朔、望
+    syzygies: Vec<SyzygyResponser>,
+}
+
+impl ApsidesResponser {
+    pub fn new(apsides: Vec<ApsisResponser>, syzygies: Vec<SyzygyResponser>) -> Self {
+        Self { apsides, syzygies }
+    }
+}
+
+/// 一年的二分二至及四季长度
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct SeasonsResponser {
+    year: i32,
+    /// 春分，请求时区的时间
+    march_equinox: HoroDateTime,
+    /// 夏至
+    june_solstice: HoroDateTime,
+    /// 秋分
+    september_equinox: HoroDateTime,
+    /// 冬至
+    december_solstice: HoroDateTime,
+    /// 春季长度，自春分至夏至，单位：日
+    spring: f64,
+    /// 夏季长度，自夏至至秋分
+    summer: f64,
+    /// 秋季长度，自秋分至冬至
+    autumn: f64,
+    /// 冬季长度，自冬至至次年春分
+    winter: f64,
+}
+
+impl SeasonsResponser {
+    pub fn new(seasons: &Seasons, tz: f64) -> Result<Self, Error> {
+        let [spring, summer, autumn, winter] = seasons.lengths();
+        Ok(Self {
+            year: seasons.year,
+            march_equinox: HoroDateTime::from_jd_zone(seasons.points[0], tz)?,
+            june_solstice: HoroDateTime::from_jd_zone(seasons.points[1], tz)?,
+            september_equinox: HoroDateTime::from_jd_zone(seasons.points[2], tz)?,
+            december_solstice: HoroDateTime::from_jd_zone(seasons.points[3], tz)?,
+            spring,
+            summer,
+            autumn,
+            winter,
+        })
+    }
+}
+
+/// 一日内星体的升、上中天、落
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct RiseSetResponser {
+    /// 星体，如：太阳
+    body: &'static str,
+    /// 当日0时，请求时区的时间
+    date: HoroDateTime,
+    /// 升，当日不升时省略
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rise: Option<HoroDateTime>,
+    /// 上中天
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transit: Option<HoroDateTime>,
+    /// 落，当日不落时省略
+    #[serde(skip_serializing_if = "Option::is_none")]
+    set: Option<HoroDateTime>,
+    status: HorizonStatus,
+}
+
+impl RiseSetResponser {
+    pub fn new(
+        body: &'static str,
+        date: HoroDateTime,
+        rise_set: &RiseSet,
+        tz: f64,
+    ) -> Result<Self, Error> {
+        let to_date = |jd: Option<f64>| jd.map(|jd| HoroDateTime::from_jd_zone(jd, tz)).transpose();
+        Ok(Self {
+            body,
+            date,
+            rise: to_date(rise_set.rise)?,
+            transit: to_date(rise_set.transit)?,
+            set: to_date(rise_set.set)?,
+            status: rise_set.status,
+        })
+    }
+}
+
+/// 晨昏蒙影
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct TwilightResponser {
+    kind: TwilightKind,
+    /// 太阳中心高度，单位：度
+    altitude: f64,
+    /// 晨光始，当日没有时省略
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dawn: Option<HoroDateTime>,
+    /// 昏影终，当日没有时省略
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dusk: Option<HoroDateTime>,
+    /// always_up为晨昏蒙影终夜不止，always_down为太阳终日在此高度之下
+    status: HorizonStatus,
+}
+
+impl TwilightResponser {
+    pub fn new(twilight: &Twilight, tz: f64) -> Result<Self, Error> {
+        let to_date = |jd: Option<f64>| jd.map(|jd| HoroDateTime::from_jd_zone(jd, tz)).transpose();
+        Ok(Self {
+            kind: twilight.kind,
+            altitude: twilight.altitude,
+            dawn: to_date(twilight.dawn)?,
+            dusk: to_date(twilight.dusk)?,
+            status: twilight.status,
+        })
+    }
+}
+
+/// 一日内的晨昏蒙影
+#[derive(Serialize)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct DailyTwilightResponser {
+    /// 当日0时，请求时区的时间
+    date: HoroDateTime,
+    twilights: Vec<TwilightResponser>,
+}
+
+impl DailyTwilightResponser {
+    pub fn new(date: HoroDateTime, twilights: Vec<TwilightResponser>) -> Self {
+        Self { date, twilights }
     }
 }
@@ -0,0 +1,352 @@
+use serde::Serialize;
+use swe::Body;
+
+use crate::{
+    ephemeris::Ephemeris,
+    error::Error,
+    request::{Disc, Observer},
+};
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+// swe_rise_trans()的计算标志，与SE_CALC_*、SE_BIT_*相同
+const SE_CALC_RISE: i32 = 1;
+const SE_CALC_SET: i32 = 2;
+const SE_CALC_MTRANSIT: i32 = 4;
+const SE_BIT_DISC_CENTER: i32 = 256;
+const SE_BIT_NO_REFRACTION: i32 = 512;
+const SE_BIT_DISC_BOTTOM: i32 = 8192;
+
+/// 星体当日相对于地平的状态
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum HorizonStatus {
+    /// 当日有升落
+    Normal,
+    /// 终日在地平之上，如极昼
+    AlwaysUp,
+    /// 终日在地平之下，如极夜
+    AlwaysDown,
+}
+
+/// 晨昏蒙影
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum TwilightKind {
+    /// 民用晨昏蒙影，太阳中心高度-6度
+    Civil,
+    /// 航海晨昏蒙影，-12度
+    Nautical,
+    /// 天文晨昏蒙影，-18度
+    Astronomical,
+    /// 自定义高度
+    Custom,
+}
+
+/// 民用、航海、天文晨昏蒙影，及其太阳中心高度
+const STANDARD_TWILIGHTS: [(TwilightKind, f64); 3] = [
+    (TwilightKind::Civil, -6.0),
+    (TwilightKind::Nautical, -12.0),
+    (TwilightKind::Astronomical, -18.0),
+];
+
+/// 一日内的晨昏蒙影
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Twilight {
+    pub kind: TwilightKind,
+    /// 太阳中心高度，单位：度
+    pub altitude: f64,
+    /// 晨光始，即太阳中心升至altitude，UTC时的儒略日，当日没有时为None
+    pub dawn: Option<f64>,
+    /// 昏影终，即太阳中心落至altitude
+    pub dusk: Option<f64>,
+    /// 太阳中心终日在altitude之上时为always_up，即晨昏蒙影终夜不止
+    /// 终日在altitude之下时为always_down
+    pub status: HorizonStatus,
+}
+
+/// 观测者的地平
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Horizon {
+    /// 观测者的地理经度、纬度、海拔
+    pub geopos: [f64; 3],
+    /// 地平高度，单位：度，升落为0，晨昏蒙影为负值
+    pub height: f64,
+    /// SE_BIT_*标志，给出是否计大气折射，及以日面、月面的上缘、中心或下缘为准
+    pub bits: i32,
+}
+
+impl Horizon {
+    pub fn new(observer: &Observer, height: f64, refraction: bool, disc: Disc) -> Self {
+        let mut bits = match disc {
+            Disc::UpperLimb => 0,
+            Disc::Center => SE_BIT_DISC_CENTER,
+            Disc::LowerLimb => SE_BIT_DISC_BOTTOM,
+        };
+        if !refraction {
+            bits |= SE_BIT_NO_REFRACTION;
+        }
+        Self {
+            geopos: [observer.long, observer.lat, observer.alt],
+            height,
+            bits,
+        }
+    }
+}
+
+/// 一日内的升、中天、落
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RiseSet {
+    /// 升，UTC时的儒略日，当日不升时为None
+    pub rise: Option<f64>,
+    /// 上中天
+    pub transit: Option<f64>,
+    /// 落
+    pub set: Option<f64>,
+    pub status: HorizonStatus,
+}
+
+/// 求[day_start, day_start+1)内星体的升、上中天、落
+/// 当日不升或不落时，rise或set为None，如月亮约每月有一日不升、一日不落
+/// 在极昼、极夜的首末日，swe可能只对升、落之一返回-2，此时另一事件仍在当日，status为normal
+pub fn rise_set(
+    ephemeris: &Ephemeris,
+    body: &Body,
+    horizon: &Horizon,
+    day_start: f64,
+) -> Result<RiseSet, Error> {
+    let (rise, set, status) = rise_and_set(ephemeris, body, horizon, day_start)?;
+    let transit = ephemeris.rise_trans(day_start, body, SE_CALC_MTRANSIT, horizon)?;
+    Ok(RiseSet {
+        rise,
+        transit: transit.filter(|&jd| jd < day_start + 1.0),
+        set,
+        status,
+    })
+}
+
+/// 求[day_start, day_start+1)内民用、航海、天文晨昏蒙影，及太阳中心高度为altitudes的晨昏蒙影
+/// 以太阳中心为准，不计大气折射
+pub fn twilights(
+    ephemeris: &Ephemeris,
+    observer: &Observer,
+    altitudes: &[f64],
+    day_start: f64,
+) -> Result<Vec<Twilight>, Error> {
+    let custom = altitudes
+        .iter()
+        .map(|&altitude| (TwilightKind::Custom, altitude));
+    STANDARD_TWILIGHTS
+        .into_iter()
+        .chain(custom)
+        .map(|(kind, altitude)| -> Result<Twilight, Error> {
+            let horizon = Horizon::new(observer, altitude, false, Disc::Center);
+            let (dawn, dusk, status) = rise_and_set(ephemeris, &Body::SeSun, &horizon, day_start)?;
+            Ok(Twilight {
+                kind,
+                altitude,
+                dawn,
+                dusk,
+                status,
+            })
+        })
+        .collect()
+}
+
+/// 求[day_start, day_start+1)内星体的升、落，及当日相对于地平的状态
+fn rise_and_set(
+    ephemeris: &Ephemeris,
+    body: &Body,
+    horizon: &Horizon,
+    day_start: f64,
+) -> Result<(Option<f64>, Option<f64>, HorizonStatus), Error> {
+    let rise = ephemeris.rise_trans(day_start, body, SE_CALC_RISE | horizon.bits, horizon)?;
+    let set = ephemeris.rise_trans(day_start, body, SE_CALC_SET | horizon.bits, horizon)?;
+
+    // 升、落均为-2时，星体当日不升不落，由赤纬判断终日在地平之上或之下
+    // 只有一个为-2时，星体当日仍越过地平，不视为不升不落
+    let status = if rise.is_none() && set.is_none() {
+        let dec = ephemeris.declination(day_start + 0.5, body)?;
+        circumpolar_status(dec, horizon.geopos[1], horizon.height)
+    } else {
+        HorizonStatus::Normal
+    };
+
+    let within = |jd: Option<f64>| jd.filter(|&jd| jd < day_start + 1.0);
+    Ok((within(rise), within(set), status))
+}
+
+/// 不升不落的星体，由赤纬、观测者的地理纬度及地平高度，得到其在地平之上或之下
+/// 上中天高度为90-|lat-dec|，下中天高度为|lat+dec|-90
+/// 二者的平均高于地平时为终日在地平之上，否则为终日在地平之下
+/// 地平为负值时，如天文晨昏蒙影的-18度，星体与观测者在赤道异侧也可能终日在地平之上
+fn circumpolar_status(dec: f64, lat: f64, height: f64) -> HorizonStatus {
+    let upper = 90.0 - (lat - dec).abs();
+    let lower = (lat + dec).abs() - 90.0;
+    // 下中天高于地平，或上中天低于地平时，平均高度与二者同侧
+    // 二者在地平两侧，仅由大气折射、日面半径等引起，以平均高度判断
+    if (upper + lower) / 2.0 > height {
+        HorizonStatus::AlwaysUp
+    } else {
+        HorizonStatus::AlwaysDown
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use swe::Body;
+
+    use super::{circumpolar_status, rise_set, twilights, Horizon, HorizonStatus, TwilightKind};
+    use crate::{
+        request::{Disc, Observer},
+        test_util::moshier,
+    };
+
+    /// 伦敦格林尼治
+    const GREENWICH: Observer = Observer {
+        lat: 51.4769,
+        long: 0.0,
+        alt: 0.0,
+    };
+
+    /// 斯瓦尔巴朗伊尔城，北纬78度
+    const LONGYEARBYEN: Observer = Observer {
+        lat: 78.22,
+        long: 15.63,
+        alt: 0.0,
+    };
+
+    /// 挪威特罗姆瑟，北极圈内
+    const TROMSO: Observer = Observer {
+        lat: 69.65,
+        long: 18.96,
+        alt: 0.0,
+    };
+
+    #[test]
+    fn test_circumpolar_status() {
+        // 北极夏至
+        assert_eq!(HorizonStatus::AlwaysUp, circumpolar_status(23.4, 89.0, 0.0));
+        // 北极冬至
+        assert_eq!(
+            HorizonStatus::AlwaysDown,
+            circumpolar_status(-23.4, 89.0, 0.0)
+        );
+        // 南极冬至
+        assert_eq!(
+            HorizonStatus::AlwaysUp,
+            circumpolar_status(-23.4, -89.0, 0.0)
+        );
+        assert_eq!(
+            HorizonStatus::AlwaysDown,
+            circumpolar_status(23.4, -89.0, 0.0)
+        );
+
+        // 北纬80度，赤纬-2度，下中天高度-12度，天文晨昏蒙影终夜不止
+        assert_eq!(
+            HorizonStatus::AlwaysUp,
+            circumpolar_status(-2.0, 80.0, -18.0)
+        );
+        // 北纬80度，赤纬-15度，上中天高度-5度，民用晨昏蒙影终日没有
+        assert_eq!(
+            HorizonStatus::AlwaysDown,
+            circumpolar_status(-15.0, 80.0, -6.0)
+        );
+    }
+
+    #[test]
+    fn test_horizon_bits() {
+        let observer = Observer {
+            lat: 39.9,
+            long: 116.4,
+            alt: 50.0,
+        };
+        let horizon = Horizon::new(&observer, 0.0, true, Disc::UpperLimb);
+        assert_eq!([116.4, 39.9, 50.0], horizon.geopos);
+        assert_eq!(0, horizon.bits);
+
+        // SE_BIT_DISC_CENTER | SE_BIT_NO_REFRACTION
+        let horizon = Horizon::new(&observer, -6.0, false, Disc::Center);
+        assert_eq!(256 | 512, horizon.bits);
+
+        let horizon = Horizon::new(&observer, 0.0, true, Disc::LowerLimb);
+        assert_eq!(8192, horizon.bits);
+    }
+
+    #[test]
+    fn test_sunrise_sunset() {
+        let ephemeris = moshier(vec![]);
+        let horizon = Horizon::new(&GREENWICH, 0.0, true, Disc::UpperLimb);
+
+        // 2024-06-21 00:00 UTC，日出03:43，日落20:21（UTC）
+        let day_start = 2460482.5;
+        let sun = rise_set(&ephemeris, &Body::SeSun, &horizon, day_start).unwrap();
+        assert_eq!(HorizonStatus::Normal, sun.status);
+        let minutes = |jd: Option<f64>| (jd.unwrap() - day_start) * 1440.0;
+        assert!((minutes(sun.rise) - (3.0 * 60.0 + 43.0)).abs() < 2.0);
+        assert!((minutes(sun.set) - (20.0 * 60.0 + 21.0)).abs() < 2.0);
+        // 中天约12:02
+        assert!((minutes(sun.transit) - (12.0 * 60.0 + 2.0)).abs() < 2.0);
+    }
+
+    #[test]
+    fn test_moonless_rise_day() {
+        let ephemeris = moshier(vec![]);
+        let horizon = Horizon::new(&GREENWICH, 0.0, true, Disc::UpperLimb);
+
+        // 月亮每日约迟升50分钟，2024-01-01起的29日内，至少有一日月亮不升
+        let start = 2460310.5;
+        let mut days = 0;
+        for i in 0..29 {
+            let moon = rise_set(&ephemeris, &Body::SeMoon, &horizon, start + f64::from(i)).unwrap();
+            assert_eq!(HorizonStatus::Normal, moon.status);
+            if moon.rise.is_none() {
+                // 当日不升，但仍有落
+                assert!(moon.set.is_some());
+                days += 1;
+            }
+        }
+        assert!((1..=2).contains(&days), "{days}");
+    }
+
+    #[test]
+    fn test_polar_day_night() {
+        let ephemeris = moshier(vec![]);
+        let horizon = Horizon::new(&TROMSO, 0.0, true, Disc::UpperLimb);
+
+        // 2024-06-21，极昼
+        let sun = rise_set(&ephemeris, &Body::SeSun, &horizon, 2460482.5).unwrap();
+        assert_eq!(HorizonStatus::AlwaysUp, sun.status);
+        assert_eq!((None, None), (sun.rise, sun.set));
+
+        // 2024-12-21，极夜
+        let sun = rise_set(&ephemeris, &Body::SeSun, &horizon, 2460665.5).unwrap();
+        assert_eq!(HorizonStatus::AlwaysDown, sun.status);
+        assert_eq!((None, None), (sun.rise, sun.set));
+    }
+
+    #[test]
+    fn test_all_night_twilight() {
+        let ephemeris = moshier(vec![]);
+
+        // 2024-03-15，太阳赤纬约-2度，北纬78.22度
+        // 太阳有升落，下中天高度约-14度，天文晨昏蒙影终夜不止，航海晨昏蒙影有晨光始、昏影终
+        let twilights = twilights(&ephemeris, &LONGYEARBYEN, &[], 2460384.5).unwrap();
+        let find = |kind| {
+            twilights
+                .iter()
+                .find(|twilight| twilight.kind == kind)
+                .unwrap()
+        };
+        let astronomical = find(TwilightKind::Astronomical);
+        assert_eq!(HorizonStatus::AlwaysUp, astronomical.status);
+        assert_eq!((None, None), (astronomical.dawn, astronomical.dusk));
+
+        let nautical = find(TwilightKind::Nautical);
+        assert_eq!(HorizonStatus::Normal, nautical.status);
+        assert!(nautical.dawn.is_some() && nautical.dusk.is_some());
+    }
+}
@@ -1,9 +1,40 @@
 use actix_web::web;
 
-use crate::handlers::{moon_long, new_moon_long, sun_long};
+use crate::handlers::{
+    aspect_events, ayanamsa, body_long, eclipse_events, ecliptic_stars, ganzhi, gregorian_to_lunar,
+    ingress_events, local_eclipse_circumstances, lunar_apsis_events, lunar_phase_events,
+    lunar_to_gregorian, lunations, mean_apogee_long, mean_node_long, moon_long, new_moon_long,
+    oscu_apogee_long, rise_set_events, season_events, solar_term_events, solar_time_conversion,
+    star_long, station_events, sun_long, true_node_long, twilight_events, void_of_course_events,
+};
 
 pub fn api_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(sun_long)
         .service(moon_long)
-        .service(new_moon_long);
+        .service(new_moon_long)
+        .service(mean_node_long)
+        .service(true_node_long)
+        .service(mean_apogee_long)
+        .service(oscu_apogee_long)
+        .service(body_long)
+        .service(star_long)
+        .service(ecliptic_stars)
+        .service(ayanamsa)
+        .service(lunations)
+        .service(lunar_phase_events)
+        .service(solar_term_events)
+        .service(gregorian_to_lunar)
+        .service(lunar_to_gregorian)
+        .service(ganzhi)
+        .service(solar_time_conversion)
+        .service(eclipse_events)
+        .service(local_eclipse_circumstances)
+        .service(ingress_events)
+        .service(void_of_course_events)
+        .service(station_events)
+        .service(aspect_events)
+        .service(lunar_apsis_events)
+        .service(season_events)
+        .service(rise_set_events)
+        .service(twilight_events);
 }
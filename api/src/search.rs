@@ -0,0 +1,196 @@
+use swe::swe_degnorm;
+
+use crate::error::Error;
+
+/// 求根的精度，单位：日，约0.01秒
+const PRECISION: f64 = 1e-7;
+
+/// 二分法求f(jd)=0的根
+/// f(jd0)与f(jd1)须异号
+pub fn bisect(f: impl Fn(f64) -> Result<f64, Error>, jd0: f64, jd1: f64) -> Result<f64, Error> {
+    let mut jd0 = jd0;
+    let mut jd1 = jd1;
+    let mut y0 = f(jd0)?;
+    while jd1 - jd0 > PRECISION {
+        let jd = (jd0 + jd1) / 2.0;
+        let y = f(jd)?;
+        if (y < 0.0) == (y0 < 0.0) {
+            jd0 = jd;
+            y0 = y;
+        } else {
+            jd1 = jd;
+        }
+    }
+    Ok((jd0 + jd1) / 2.0)
+}
+
+/// 角度差a-b，归算到[-180, 180)
+pub fn angle_diff(a: f64, b: f64) -> f64 {
+    swe_degnorm(a - b + 180.0) - 180.0
+}
+
+/// 在[start, end]内，以step为步长，求f(jd)=0的时刻
+/// step须小于相邻两根的间隔
+pub fn crossings(
+    f: impl Fn(f64) -> Result<f64, Error>,
+    start: f64,
+    end: f64,
+    step: f64,
+) -> Result<Vec<f64>, Error> {
+    find_roots(&f, start, end, step, |_, _| true)
+}
+
+/// 在[start, end]内，以step为步长，求角度f(jd)等于target的时刻
+/// step须小于相邻两次到达target的间隔
+pub fn angle_crossings(
+    f: impl Fn(f64) -> Result<f64, Error>,
+    target: f64,
+    start: f64,
+    end: f64,
+    step: f64,
+) -> Result<Vec<f64>, Error> {
+    let g = |jd| Ok(angle_diff(f(jd)?, target));
+    // 角度差由-180跳变到180，或由180跳变到-180时，不是根
+    find_roots(&g, start, end, step, |y0, y1| (y1 - y0).abs() < 180.0)
+}
+
+/// 在[start, end]内，以step为步长，求角度f(jd)越过width整数倍的时刻
+/// 返回越过后所在的区间序号，即floor(f/width)，及时刻
+/// step须小于相邻两次越界的间隔
+pub fn boundary_crossings(
+    f: impl Fn(f64) -> Result<f64, Error>,
+    width: f64,
+    start: f64,
+    end: f64,
+    step: f64,
+) -> Result<Vec<(usize, f64)>, Error> {
+    let n = (360.0 / width).round() as usize;
+    let index = |angle: f64| (swe_degnorm(angle) / width).floor() as usize % n;
+
+    let mut crossings = vec![];
+    let mut jd0 = start;
+    let mut angle0 = f(jd0)?;
+    while jd0 < end {
+        let jd1 = (jd0 + step).min(end);
+        let angle1 = f(jd1)?;
+        let (i0, i1) = (index(angle0), index(angle1));
+        if i0 != i1 {
+            // 顺行越过区间i1的起点，逆行越过区间i0的起点
+            let boundary = if angle_diff(angle1, angle0) > 0.0 {
+                i1 as f64 * width
+            } else {
+                i0 as f64 * width
+            };
+            let jd = bisect(|jd| Ok(angle_diff(f(jd)?, boundary)), jd0, jd1)?;
+            crossings.push((i1, jd));
+        }
+        jd0 = jd1;
+        angle0 = angle1;
+    }
+    Ok(crossings)
+}
+
+fn find_roots(
+    f: &impl Fn(f64) -> Result<f64, Error>,
+    start: f64,
+    end: f64,
+    step: f64,
+    is_continuous: impl Fn(f64, f64) -> bool,
+) -> Result<Vec<f64>, Error> {
+    let mut roots = vec![];
+    let mut jd0 = start;
+    let mut y0 = f(jd0)?;
+    while jd0 < end {
+        let jd1 = (jd0 + step).min(end);
+        let y1 = f(jd1)?;
+        let is_sign_changed = (y0 < 0.0 && y1 >= 0.0) || (y0 > 0.0 && y1 <= 0.0);
+        if is_sign_changed && is_continuous(y0, y1) {
+            roots.push(bisect(f, jd0, jd1)?);
+        }
+        jd0 = jd1;
+        y0 = y1;
+    }
+    Ok(roots)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{angle_crossings, angle_diff, bisect, boundary_crossings, crossings};
+    use swe::swe_degnorm;
+
+    #[test]
+    fn test_bisect() {
+        let root = bisect(|x| Ok(x * x - 2.0), 0.0, 2.0);
+        assert!(root.is_ok());
+        assert!((root.unwrap() - 2f64.sqrt()).abs() < 1e-6);
+
+        // 减函数
+        let root = bisect(|x| Ok(1.0 - x), 0.0, 3.0);
+        assert!(root.is_ok());
+        assert!((root.unwrap() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_angle_diff() {
+        assert_eq!(10.0, angle_diff(20.0, 10.0));
+        assert_eq!(-10.0, angle_diff(10.0, 20.0));
+        assert_eq!(20.0, angle_diff(10.0, 350.0));
+        assert_eq!(-20.0, angle_diff(350.0, 10.0));
+        assert_eq!(-180.0, angle_diff(180.0, 0.0));
+    }
+
+    #[test]
+    fn test_crossings() {
+        // sin(x)在(0, 10]内的根：π、2π、3π
+        let roots = crossings(|x| Ok(x.sin()), 0.5, 10.0, 0.5);
+        assert!(roots.is_ok());
+        let roots = roots.unwrap();
+        assert_eq!(3, roots.len());
+        for (i, root) in roots.iter().enumerate() {
+            let expected = std::f64::consts::PI * (i + 1) as f64;
+            assert!((root - expected).abs() < 1e-6, "{root}");
+        }
+    }
+
+    #[test]
+    fn test_angle_crossings() {
+        // 每日增加13度
+        let f = |jd: f64| Ok(swe_degnorm(jd * 13.0));
+        let roots = angle_crossings(f, 0.0, 1.0, 60.0, 1.0);
+        assert!(roots.is_ok());
+        let roots = roots.unwrap();
+        assert_eq!(2, roots.len());
+        assert!((roots[0] - 360.0 / 13.0).abs() < 1e-6);
+        assert!((roots[1] - 720.0 / 13.0).abs() < 1e-6);
+
+        // f由360跳变到0时，角度差跳变，不是根
+        let roots = angle_crossings(f, 180.0, 1.0, 40.0, 1.0);
+        assert!(roots.is_ok());
+        let roots = roots.unwrap();
+        assert_eq!(1, roots.len());
+        assert!((roots[0] - 180.0 / 13.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_boundary_crossings() {
+        // 每日增加1度，越过15度整数倍
+        let crossings = boundary_crossings(|jd| Ok(swe_degnorm(jd)), 15.0, 350.0, 380.0, 1.0);
+        assert!(crossings.is_ok());
+        let crossings = crossings.unwrap();
+        assert_eq!(2, crossings.len());
+        assert_eq!(0, crossings[0].0);
+        assert!((crossings[0].1 - 360.0).abs() < 1e-6);
+        assert_eq!(1, crossings[1].0);
+        assert!((crossings[1].1 - 375.0).abs() < 1e-6);
+
+        // 逆行
+        let crossings = boundary_crossings(|jd| Ok(swe_degnorm(-jd)), 30.0, -5.5, 40.0, 1.0);
+        assert!(crossings.is_ok());
+        let crossings = crossings.unwrap();
+        assert_eq!(2, crossings.len());
+        assert_eq!(11, crossings[0].0);
+        assert!(crossings[0].1.abs() < 1e-6);
+        assert_eq!(10, crossings[1].0);
+        assert!((crossings[1].1 - 30.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,148 @@
+use swe::Body;
+
+use crate::{
+    ephemeris::Ephemeris, error::Error, horo_date_time::HoroDateTime, search::boundary_crossings,
+};
+
+/// 二十四节气，以太阳黄经0度的春分为首，每15度一个
+pub const SOLAR_TERM_NAMES: [&str; 24] = [
+    "春分", "清明", "谷雨", "立夏", "小满", "芒种", "夏至", "小暑", "大暑", "立秋", "处暑", "白露",
+    "秋分", "寒露", "霜降", "立冬", "小雪", "大雪", "冬至", "小寒", "大寒", "立春", "雨水", "惊蛰",
+];
+
+/// 节气
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SolarTerm {
+    /// 序号，0为春分，太阳黄经=序号*15
+    pub index: usize,
+    /// UTC时的儒略日
+    pub jd_utc: f64,
+}
+
+impl SolarTerm {
+    /// 节气名
+    pub fn name(&self) -> &'static str {
+        SOLAR_TERM_NAMES[self.index]
+    }
+
+    /// 太阳黄经，单位：度
+    pub fn long(&self) -> f64 {
+        self.index as f64 * 15.0
+    }
+
+    /// 是否为中气，太阳黄经为30度的整数倍时为中气，否则为节
+    pub fn is_zhong_qi(&self) -> bool {
+        self.index.is_multiple_of(2)
+    }
+}
+
+/// 一年的二分二至
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Seasons {
+    pub year: i32,
+    /// 春分、夏至、秋分、冬至，及次年春分，UTC时的儒略日
+    pub points: [f64; 5],
+}
+
+impl Seasons {
+    /// 春、夏、秋、冬四季的长度，单位：日
+    /// 春季自春分至夏至，冬季自冬至至次年春分
+    pub fn lengths(&self) -> [f64; 4] {
+        std::array::from_fn(|i| self.points[i + 1] - self.points[i])
+    }
+}
+
+/// 太阳黄经
+pub fn sun_long(ephemeris: &Ephemeris, jd_utc: f64) -> Result<f64, Error> {
+    let xx = ephemeris.calc(jd_utc, &Body::SeSun)?;
+    Ok(xx[0])
+}
+
+/// 求[start, end]内的节气，按时间排序
+pub fn solar_terms(ephemeris: &Ephemeris, start: f64, end: f64) -> Result<Vec<SolarTerm>, Error> {
+    // 太阳每日约行1度，以1日为步长
+    let terms = boundary_crossings(|jd| sun_long(ephemeris, jd), 15.0, start, end, 1.0)?
+        .into_iter()
+        .map(|(index, jd_utc)| SolarTerm { index, jd_utc })
+        .collect();
+    Ok(terms)
+}
+
+/// 求start_year至end_year各年的二分二至，即太阳黄经为0、90、180、270度的时刻
+pub fn seasons(
+    ephemeris: &Ephemeris,
+    start_year: i32,
+    end_year: i32,
+) -> Result<Vec<Seasons>, Error> {
+    // 春分在3月20日前后
+    let start = HoroDateTime::new(start_year, 3, 1, 0, 0, 0, 0.0)?.jd_utc;
+    let end = HoroDateTime::new(end_year + 1, 4, 1, 0, 0, 0, 0.0)?.jd_utc;
+    let points: Vec<f64> = boundary_crossings(|jd| sun_long(ephemeris, jd), 90.0, start, end, 1.0)?
+        .into_iter()
+        .map(|(_, jd_utc)| jd_utc)
+        .collect();
+
+    let n = (end_year - start_year + 1) as usize;
+    if points.len() != 4 * n + 1 {
+        return Err(Error::Function(format!(
+            "计算{start_year}-{end_year}年二分二至错误"
+        )));
+    }
+    let seasons = (0..n)
+        .map(|i| Seasons {
+            year: start_year + i as i32,
+            points: std::array::from_fn(|k| points[4 * i + k]),
+        })
+        .collect();
+    Ok(seasons)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{seasons, Seasons};
+    use crate::test_util::moshier;
+
+    #[test]
+    fn test_season_lengths() {
+        // 2023年：春分2023-03-20 21:24，夏至06-21 14:57，秋分09-23 06:50，冬至12-22 03:27，2024年春分03-20 03:06（UTC）
+        let seasons = Seasons {
+            year: 2023,
+            points: [
+                2460024.39167,
+                2460117.12292,
+                2460210.78472,
+                2460300.64375,
+                2460389.62917,
+            ],
+        };
+        let lengths = seasons.lengths();
+        let expected = [92.73125, 93.66180, 89.85903, 88.98542];
+        for (length, expected) in lengths.iter().zip(expected) {
+            assert!((length - expected).abs() < 1e-4, "{length}");
+        }
+    }
+
+    #[test]
+    fn test_seasons() {
+        let ephemeris = moshier(vec![]);
+
+        let seasons = seasons(&ephemeris, 2023, 2024).unwrap();
+        assert_eq!(2, seasons.len());
+        assert_eq!(2023, seasons[0].year);
+        assert_eq!(2024, seasons[1].year);
+
+        // 2023年二分二至，及2024年春分，UTC时，精确到分钟
+        let expected = [
+            2460024.39167,
+            2460117.12292,
+            2460210.78472,
+            2460300.64375,
+            2460389.62917,
+        ];
+        for (jd, expected) in seasons[0].points.iter().zip(expected) {
+            assert!((jd - expected).abs() < 1.0 / 1440.0, "{jd}");
+        }
+        // 相邻两年首尾相接
+        assert_eq!(seasons[0].points[4], seasons[1].points[0]);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{ephemeris::Ephemeris, error::Error, horo_date_time::HoroDateTime};
+
+/// 地方时
+#[derive(Clone, Debug)]
+pub struct SolarTime {
+    /// 地方平太阳时
+    pub lmt: HoroDateTime,
+    /// 真太阳时，儒略日为实际时刻，时区为经度/15+时差
+    pub true_solar_time: HoroDateTime,
+    /// 时差，真太阳时-平太阳时，单位：分钟
+    pub equation_of_time: f64,
+}
+
+/// 由UTC时间及地理经度计算地方平太阳时、真太阳时
+/// long: 地理经度，东经为正，西经为负
+pub fn solar_time(ephemeris: &Ephemeris, jd_utc: f64, long: f64) -> Result<SolarTime, Error> {
+    // 地方平太阳时，以经度/15为时区
+    let tz = long / 15.0;
+    let lmt = HoroDateTime::from_jd_zone(jd_utc, tz)?;
+
+    // 时差，单位：日
+    let e = ephemeris.time_equ(jd_utc)?;
+    // 经度±180度时，tz+时差可能超出时区范围，因此以平移的儒略日得到日期时间，再还原儒略日
+    let mut true_solar_time = HoroDateTime::from_jd_zone(jd_utc + e, tz)?;
+    true_solar_time.tz = tz + e * 24.0;
+    true_solar_time.jd_utc = lmt.jd_utc;
+    true_solar_time.jd_et = lmt.jd_et;
+    true_solar_time.jd_ut1 = lmt.jd_ut1;
+
+    Ok(SolarTime {
+        lmt,
+        true_solar_time,
+        equation_of_time: e * 1440.0,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::solar_time;
+    use crate::{horo_date_time::HoroDateTime, test_util::moshier};
+
+    /// 当日时刻，单位：秒
+    fn seconds_of_day(t: &HoroDateTime) -> f64 {
+        f64::from(t.hour) * 3600.0
+            + f64::from(t.minute) * 60.0
+            + f64::from(t.second)
+            + t.ms / 1000.0
+    }
+
+    #[test]
+    fn test_solar_time() {
+        let ephemeris = moshier(vec![]);
+
+        // 2024-11-03 02:24 UTC，东经120度，地方平太阳时10:24，时差约+16.4分钟
+        let jd_utc = 2460617.6;
+        let t = solar_time(&ephemeris, jd_utc, 120.0).unwrap();
+        assert_eq!(8.0, t.lmt.tz);
+        assert_eq!((2024, 11, 3), (t.lmt.year, t.lmt.month, t.lmt.day));
+        let lmt = seconds_of_day(&t.lmt);
+        assert!((lmt - 10.4 * 3600.0).abs() < 1.0, "{}", lmt);
+        assert!(
+            (t.equation_of_time - 16.4).abs() < 0.3,
+            "{}",
+            t.equation_of_time
+        );
+        // 真太阳时=平太阳时+时差，儒略日不变
+        assert_eq!(t.lmt.jd_utc, t.true_solar_time.jd_utc);
+        assert!((t.true_solar_time.tz - (8.0 + t.equation_of_time / 60.0)).abs() < 1e-9);
+        let d = seconds_of_day(&t.true_solar_time) - lmt;
+        assert!((d - t.equation_of_time * 60.0).abs() < 1.0, "{}", d);
+
+        // 2024-02-12 12:00 UTC，西经75度，地方平太阳时07:00，时差约-14.2分钟
+        let jd_utc = 2460353.0;
+        let t = solar_time(&ephemeris, jd_utc, -75.0).unwrap();
+        assert_eq!(-5.0, t.lmt.tz);
+        assert_eq!((2024, 2, 12), (t.lmt.year, t.lmt.month, t.lmt.day));
+        let lmt = seconds_of_day(&t.lmt);
+        assert!((lmt - 7.0 * 3600.0).abs() < 1.0, "{}", lmt);
+        assert!(
+            (t.equation_of_time + 14.2).abs() < 0.3,
+            "{}",
+            t.equation_of_time
+        );
+        assert_eq!(t.lmt.jd_utc, t.true_solar_time.jd_utc);
+        let d = seconds_of_day(&t.true_solar_time) - lmt;
+        assert!((d - t.equation_of_time * 60.0).abs() < 1.0, "{}", d);
+    }
+}
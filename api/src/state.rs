@@ -1,3 +1,46 @@
+use std::{fmt::Display, str::FromStr};
+
 pub struct AppState {
     pub ephe_path: String,
+    /// 星历表
+    pub backend: Backend,
+    /// JPL星历表文件名，backend=jpl时使用
+    pub jpl_file: Option<String>,
+    /// 星历表文件计算失败时，是否改用Moshier星历表
+    pub fallback: bool,
+}
+
+/// 星历表
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Backend {
+    /// 瑞士星历表文件，*.se1
+    Swiss,
+    /// Moshier星历表，无需星历表文件
+    Moshier,
+    /// JPL星历表文件
+    Jpl,
+}
+
+impl FromStr for Backend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "swiss" => Ok(Self::Swiss),
+            "moshier" => Ok(Self::Moshier),
+            "jpl" => Ok(Self::Jpl),
+            _ => Err(format!("不支持的星历表:{s}，只能为swiss、moshier、jpl")),
+        }
+    }
+}
+
+impl Display for Backend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Backend::Swiss => "swiss",
+            Backend::Moshier => "moshier",
+            Backend::Jpl => "jpl",
+        };
+        write!(f, "{}", s)
+    }
 }
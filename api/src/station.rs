@@ -0,0 +1,203 @@
+use serde::Serialize;
+use swe::Body;
+
+use crate::{
+    body::body_name,
+    ephemeris::Ephemeris,
+    error::Error,
+    search::{angle_crossings, crossings},
+};
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// 求留的行星
+const STATION_BODIES: [Body; 8] = [
+    Body::SeMercury,
+    Body::SeVenus,
+    Body::SeMars,
+    Body::SeJupiter,
+    Body::SeSaturn,
+    Body::SeUranus,
+    Body::SeNeptune,
+    Body::SePluto,
+];
+
+/// 逆行的最长时间，单位：日，冥王星逆行约160日
+const MAX_RETROGRADE_DAYS: f64 = 170.0;
+
+/// 没有前、后一次留时，影子期的最长时间，单位：日
+const MAX_SHADOW_DAYS: f64 = 366.0;
+
+/// 留的类型
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum StationKind {
+    /// 顺行转逆行
+    Retrograde,
+    /// 逆行转顺行
+    Direct,
+}
+
+impl StationKind {
+    /// 由留之后的经度速度得到留的类型
+    pub fn from_speed(speed: f64) -> Self {
+        if speed < 0.0 {
+            Self::Retrograde
+        } else {
+            Self::Direct
+        }
+    }
+
+    /// 另一种留，即下一个留的类型
+    pub fn opposite(&self) -> Self {
+        match self {
+            Self::Retrograde => Self::Direct,
+            Self::Direct => Self::Retrograde,
+        }
+    }
+}
+
+/// 留
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Station {
+    /// 行星中文名
+    pub body: &'static str,
+    pub kind: StationKind,
+    /// UTC时的儒略日
+    pub jd_utc: f64,
+    /// 黄道经度，单位：度
+    pub long: f64,
+    /// 影子期的起止时刻，UTC时的儒略日
+    /// 顺行转逆行时为前影子期：自行星首次到达逆行转顺行的黄经，至本次留
+    /// 逆行转顺行时为后影子期：自本次留，至行星回到顺行转逆行的黄经
+    pub shadow: Option<(f64, f64)>,
+}
+
+/// 求[start, end]内各行星的留，按时间排序
+pub fn stations(ephemeris: &Ephemeris, start: f64, end: f64) -> Result<Vec<Station>, Error> {
+    let mut stations = vec![];
+    for body in &STATION_BODIES {
+        stations.extend(body_stations(ephemeris, body, start, end)?);
+    }
+    stations.sort_by(|a, b| a.jd_utc.total_cmp(&b.jd_utc));
+    Ok(stations)
+}
+
+/// 求[start, end]内行星的留
+/// ephemeris须计算速度
+fn body_stations(
+    ephemeris: &Ephemeris,
+    body: &Body,
+    start: f64,
+    end: f64,
+) -> Result<Vec<Station>, Error> {
+    let long = |jd| Ok(ephemeris.calc(jd, body)?[0]);
+    let speed = |jd| Ok(ephemeris.calc(jd, body)?[3]);
+
+    // 前后多算一次逆行的时间，以得到区间两端的留所对应的另一个留
+    let search_start = start - MAX_RETROGRADE_DAYS;
+    let jds = crossings(speed, search_start, end + MAX_RETROGRADE_DAYS, 1.0)?;
+    // 顺转逆、逆转顺交替出现，由起点的速度得到第一个留的类型，每个留只需计算一次黄经
+    let mut kind = StationKind::from_speed(-speed(search_start)?);
+    let mut all = vec![];
+    for jd in jds {
+        all.push((kind, jd, long(jd)?));
+        kind = kind.opposite();
+    }
+
+    let mut stations = vec![];
+    for (i, &(kind, jd, l)) in all.iter().enumerate() {
+        if jd < start || jd > end {
+            continue;
+        }
+        // 影子期在本次留与前、后一次留之间，没有前、后一次留时，以MAX_SHADOW_DAYS为限
+        let prev = i.checked_sub(1).map(|j| all[j]);
+        let next = all.get(i + 1).copied();
+        let shadow = match kind {
+            StationKind::Retrograde => match next {
+                Some((_, _, direct_long)) => {
+                    let from = prev.map_or(jd - MAX_SHADOW_DAYS, |(_, prev_jd, _)| prev_jd);
+                    angle_crossings(long, direct_long, from, jd, 1.0)?
+                        .last()
+                        .map(|&shadow_start| (shadow_start, jd))
+                }
+                None => None,
+            },
+            StationKind::Direct => match prev {
+                Some((_, _, retrograde_long)) => {
+                    let to = next.map_or(jd + MAX_SHADOW_DAYS, |(_, next_jd, _)| next_jd);
+                    angle_crossings(long, retrograde_long, jd, to, 1.0)?
+                        .first()
+                        .map(|&shadow_end| (jd, shadow_end))
+                }
+                None => None,
+            },
+        };
+        stations.push(Station {
+            body: body_name(body),
+            kind,
+            jd_utc: jd,
+            long: l,
+            shadow,
+        });
+    }
+    Ok(stations)
+}
+
+#[cfg(test)]
+mod test {
+    use swe::{Body, Flag};
+
+    use super::{body_stations, StationKind};
+    use crate::test_util::moshier;
+
+    #[test]
+    fn test_station_kind() {
+        assert_eq!(StationKind::Retrograde, StationKind::from_speed(-0.001));
+        assert_eq!(StationKind::Direct, StationKind::from_speed(0.001));
+        assert_eq!(StationKind::Direct, StationKind::Retrograde.opposite());
+        assert_eq!(StationKind::Retrograde, StationKind::Direct.opposite());
+    }
+
+    #[test]
+    fn test_mercury_stations() {
+        let ephemeris = moshier(vec![Flag::SeflgSpeed]);
+
+        // 2024-03-25至2024-05-01，水星2024-04-01 22:14留于白羊27°13′转逆行，
+        // 2024-04-25 12:54留于白羊15°58′转顺行（UTC）
+        let stations = body_stations(&ephemeris, &Body::SeMercury, 2460394.5, 2460431.5).unwrap();
+        assert_eq!(2, stations.len());
+
+        let retrograde = stations[0];
+        assert_eq!(StationKind::Retrograde, retrograde.kind);
+        assert!(
+            (retrograde.jd_utc - 2460402.4264).abs() < 0.1,
+            "{}",
+            retrograde.jd_utc
+        );
+        assert!(
+            (retrograde.long - 27.22).abs() < 0.05,
+            "{}",
+            retrograde.long
+        );
+
+        let direct = stations[1];
+        assert_eq!(StationKind::Direct, direct.kind);
+        assert!(
+            (direct.jd_utc - 2460426.0375).abs() < 0.1,
+            "{}",
+            direct.jd_utc
+        );
+        assert!((direct.long - 15.97).abs() < 0.05, "{}", direct.long);
+
+        // 前影子期自2024-03-18水星到达白羊15°58′起，后影子期至2024-05-13水星回到白羊27°13′止
+        let (pre_start, pre_end) = retrograde.shadow.unwrap();
+        assert!((pre_start - 2460388.0).abs() < 1.0, "{pre_start}");
+        assert_eq!(retrograde.jd_utc, pre_end);
+        let (post_start, post_end) = direct.shadow.unwrap();
+        assert_eq!(direct.jd_utc, post_start);
+        assert!((post_end - 2460444.0).abs() < 1.0, "{post_end}");
+    }
+}
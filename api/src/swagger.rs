@@ -1,15 +1,126 @@
 use crate::{
-    handlers::{__path_moon_long, __path_new_moon_long, __path_sun_long},
+    apsis::ApsisKind,
+    eclipse::{EclipseKind, EclipseType},
+    handlers::{
+        __path_aspect_events, __path_ayanamsa, __path_body_long, __path_eclipse_events,
+        __path_ecliptic_stars, __path_ganzhi, __path_gregorian_to_lunar, __path_ingress_events,
+        __path_local_eclipse_circumstances, __path_lunar_apsis_events, __path_lunar_phase_events,
+        __path_lunar_to_gregorian, __path_lunations, __path_mean_apogee_long,
+        __path_mean_node_long, __path_moon_long, __path_new_moon_long, __path_oscu_apogee_long,
+        __path_rise_set_events, __path_season_events, __path_solar_term_events,
+        __path_solar_time_conversion, __path_star_long, __path_station_events, __path_sun_long,
+        __path_true_node_long, __path_twilight_events, __path_void_of_course_events,
+    },
     horo_date_time::HoroDateTime,
-    request::{DateRangeRequest, DateRequest},
-    response::LongResponser,
+    lunar_phase::Phase,
+    request::{
+        ApsisRequest, AspectRequest, Ayanamsa, Coordinate, DateRangeRequest, DateRequest,
+        DateTimeRequest, Disc, EventRequest, Frame, LocalEclipseRequest, LunarDateRequest,
+        Observer, Output, Polar, PositionOptions, RiseSetRequest, StarsRequest, TwilightRequest,
+        YearRangeRequest, Zodiac,
+    },
+    response::{
+        ApsidesResponser, ApsisResponser, AspectResponser, AspectSampleResponser, AspectsResponser,
+        ContactResponser, DailyTwilightResponser, EclipseResponser, FourPillarsResponser,
+        IngressResponser, LocalEclipseResponser, LongResponser, LunarDateResponser,
+        LunationResponser, PillarResponser, RiseSetResponser, SeasonsResponser, ShadowResponser,
+        SolarTermResponser, SolarTimeConversionResponser, SolarTimeResponser, StarResponser,
+        StationResponser, SyzygyResponser, TwilightResponser, VoidOfCourseResponser,
+    },
+    rise_set::{HorizonStatus, TwilightKind},
+    station::StationKind,
 };
 use utoipa::OpenApi;
 
 // swagger
 #[derive(OpenApi)]
 #[openapi(
-    paths(sun_long, moon_long, new_moon_long),
-    components(schemas(LongResponser, HoroDateTime, DateRequest, DateRangeRequest))
+    paths(
+        sun_long,
+        moon_long,
+        new_moon_long,
+        mean_node_long,
+        true_node_long,
+        mean_apogee_long,
+        oscu_apogee_long,
+        body_long,
+        star_long,
+        ecliptic_stars,
+        ayanamsa,
+        lunations,
+        lunar_phase_events,
+        solar_term_events,
+        gregorian_to_lunar,
+        lunar_to_gregorian,
+        ganzhi,
+        solar_time_conversion,
+        eclipse_events,
+        local_eclipse_circumstances,
+        ingress_events,
+        void_of_course_events,
+        station_events,
+        aspect_events,
+        lunar_apsis_events,
+        season_events,
+        rise_set_events,
+        twilight_events
+    ),
+    components(schemas(
+        LongResponser,
+        StarResponser,
+        LunationResponser,
+        Phase,
+        SolarTermResponser,
+        LunarDateResponser,
+        PillarResponser,
+        FourPillarsResponser,
+        SolarTimeResponser,
+        SolarTimeConversionResponser,
+        EclipseResponser,
+        ContactResponser,
+        LocalEclipseResponser,
+        IngressResponser,
+        VoidOfCourseResponser,
+        StationResponser,
+        ShadowResponser,
+        StationKind,
+        AspectsResponser,
+        AspectResponser,
+        AspectSampleResponser,
+        ApsidesResponser,
+        ApsisResponser,
+        SyzygyResponser,
+        ApsisKind,
+        SeasonsResponser,
+        RiseSetResponser,
+        HorizonStatus,
+        DailyTwilightResponser,
+        TwilightResponser,
+        TwilightKind,
+        EclipseKind,
+        EclipseType,
+        HoroDateTime,
+        DateRequest,
+        DateRangeRequest,
+        StarsRequest,
+        AspectRequest,
+        ApsisRequest,
+        YearRangeRequest,
+        RiseSetRequest,
+        TwilightRequest,
+        EventRequest,
+        DateTimeRequest,
+        LunarDateRequest,
+        LocalEclipseRequest,
+        Output,
+        Coordinate,
+        Frame,
+        Observer,
+        Zodiac,
+        Ayanamsa,
+        PositionOptions,
+        Disc,
+        Polar
+    ))
 )]
 pub struct ApiDoc;
@@ -0,0 +1,19 @@
+use swe::Flag;
+
+use crate::{
+    ephemeris::Ephemeris,
+    state::{AppState, Backend},
+};
+
+/// Moshier星历表，无需星历表文件，用于测试
+pub static MOSHIER: AppState = AppState {
+    ephe_path: String::new(),
+    backend: Backend::Moshier,
+    jpl_file: None,
+    fallback: false,
+};
+
+/// 以Moshier星历表及给定的计算标志构造，地心、回归黄道
+pub fn moshier(flags: Vec<Flag>) -> Ephemeris<'static> {
+    Ephemeris::with_flags(&MOSHIER, flags)
+}